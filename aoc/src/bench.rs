@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use common::bench::{Baseline, Stats, BASELINE_FILE};
+use common::input::Inputs;
+
+use super::{parse_days, solution, GREEN, RED, RESET};
+
+// A median more than this fraction away from the baseline is highlighted.
+const THRESHOLD: f64 = 0.1;
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    days: Vec<u32>,
+    iterations: usize,
+    inputs_dir: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    save: bool,
+}
+
+pub fn parse_args(args: &[String]) -> Result<BenchOptions, String> {
+    let mut days = None;
+    let mut options = BenchOptions {
+        days: Vec::new(),
+        iterations: 10,
+        inputs_dir: None,
+        baseline: None,
+        save: false,
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--iterations" => {
+                let iterations = value()?;
+                options.iterations = match iterations.parse() {
+                    Ok(iterations) if iterations > 0 => iterations,
+                    _ => return Err(format!("{} is not a valid number of iterations", iterations)),
+                }
+            },
+            "--inputs-dir" => options.inputs_dir = Some(PathBuf::from(value()?)),
+            "--baseline" => options.baseline = Some(PathBuf::from(value()?)),
+            "--save" => options.save = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            day if days.is_none() => days = Some(parse_days(day)?),
+            extra => return Err(format!("Unexpected argument {}", extra)),
+        }
+    }
+
+    options.days = days.ok_or("No day given")?;
+    Ok(options)
+}
+
+// The change in median against the baseline, colored if it moved by more
+// than THRESHOLD.
+fn compare(stats: &Stats, baseline: Option<&Stats>) -> String {
+    let baseline = match baseline {
+        Some(baseline) if baseline.median > Duration::from_nanos(0) => baseline,
+        _ => return String::new(),
+    };
+    let change = stats.median.as_secs_f64() / baseline.median.as_secs_f64() - 1.0;
+    let formatted = format!("{:+.1}%", change * 100.0);
+
+    if change > THRESHOLD {
+        format!("{}{}{}", RED, formatted, RESET)
+    } else if change < -THRESHOLD {
+        format!("{}{}{}", GREEN, formatted, RESET)
+    } else {
+        formatted
+    }
+}
+
+pub fn run(options: &BenchOptions) -> Result<String, String> {
+    let inputs = Inputs::discover(options.inputs_dir.as_deref());
+    let baseline_path = options.baseline.clone().or_else(|| inputs.local_path(BASELINE_FILE));
+    let mut baseline = match &baseline_path {
+        Some(path) => Baseline::load(path)?,
+        None => Baseline::new(),
+    };
+    let mut out = String::new();
+
+    for &day in &options.days {
+        let solution = solution(day).ok_or(format!("Day {} has not been solved", day))?;
+        let timings = solution.bench(&inputs.read(day)?, options.iterations)
+            .map_err(|err| format!("Day {}: {}", day, err))?;
+
+        out.push_str(&format!(
+            "Day {} ({} iterations)\n  {:<8}{:>12}{:>12}{:>12}  {}\n",
+            day, options.iterations, "stage", "min", "median", "max", "vs baseline"
+        ));
+        for (stage, stats) in timings.stages().iter() {
+            let line = format!(
+                "  {:<8}{:>12}{:>12}{:>12}  {}",
+                stage,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max),
+                compare(stats, baseline.get(day, stage))
+            );
+
+            out.push_str(line.trim_end());
+            out.push('\n');
+
+            if options.save {
+                baseline.record(day, stage, *stats);
+            }
+        }
+    }
+
+    if options.save {
+        let path = baseline_path.ok_or("No inputs directory to store timings in; pass --baseline")?;
+
+        baseline.save(&path)?;
+        out.push_str(&format!("Saved baseline in {}\n", path.display()));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::process;
+    use crate::test::args;
+
+    fn micros(median: u64) -> Stats {
+        Stats {
+            min: Duration::from_micros(median),
+            median: Duration::from_micros(median),
+            max: Duration::from_micros(median),
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(BenchOptions{
+                days: vec![4],
+                iterations: 3,
+                inputs_dir: None,
+                baseline: Some(PathBuf::from("b.txt")),
+                save: true,
+            }),
+            parse_args(&args("4 --iterations 3 --baseline b.txt --save"))
+        );
+        assert!(parse_args(&args("4 --iterations 0")).is_err());
+        assert!(parse_args(&args("--save")).is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!("", compare(&micros(100), None));
+        assert_eq!("+5.0%", compare(&micros(105), Some(&micros(100))));
+        assert_eq!(format!("{}+50.0%{}", RED, RESET), compare(&micros(150), Some(&micros(100))));
+        assert_eq!(format!("{}-50.0%{}", GREEN, RESET), compare(&micros(50), Some(&micros(100))));
+    }
+
+    #[test]
+    fn test_run_saves_baseline() {
+        let path = env::temp_dir().join(format!("aoc-bench-{}.txt", process::id()));
+        let options = BenchOptions{
+            days: vec![1],
+            iterations: 2,
+            inputs_dir: None,
+            baseline: Some(path.clone()),
+            save: true,
+        };
+
+        let out = run(&options).unwrap();
+        assert!(out.starts_with("Day 1 (2 iterations)\n"));
+        assert!(out.contains("  part2 "));
+
+        let baseline = Baseline::load(&path).unwrap();
+        assert!(baseline.get(1, "parse").is_some());
+        assert!(baseline.get(1, "part2").is_some());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+use std::io::{stdin, BufRead};
+use std::path::PathBuf;
+
+use common::input::{self, Inputs};
+use day1::model::FuelModel;
+use day1::report::Report;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Format {
+    Table,
+    Csv,
+    Total,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FuelOptions {
+    input: Option<PathBuf>,
+    inputs_dir: Option<PathBuf>,
+    format: Format,
+    model: FuelModel,
+}
+
+pub fn parse_args(args: &[String]) -> Result<FuelOptions, String> {
+    let mut options = FuelOptions{
+        input: None,
+        inputs_dir: None,
+        format: Format::Table,
+        model: FuelModel::STANDARD,
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--input" => options.input = Some(PathBuf::from(value()?)),
+            "--inputs-dir" => options.inputs_dir = Some(PathBuf::from(value()?)),
+            "--format" => {
+                options.format = match value()?.as_str() {
+                    "table" => Format::Table,
+                    "csv" => Format::Csv,
+                    "total" => Format::Total,
+                    other => return Err(format!("Unknown format {}", other)),
+                }
+            },
+            "--model" => options.model = FuelModel::parse(value()?)?,
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+    Ok(options)
+}
+
+fn format_report(report: &Report, format: Format) -> String {
+    match format {
+        Format::Table => report.table(),
+        Format::Csv => report.csv(),
+        Format::Total => format!("Fuel: {}\nWith fuel for fuel: {}\n", report.naive, report.recursive),
+    }
+}
+
+pub fn run(options: &FuelOptions) -> Result<String, String> {
+    let report = match &options.input {
+        // Stream masses from stdin as they arrive.
+        Some(path) if path.as_os_str() == "-" => {
+            let stdin = stdin();
+            let mut error = None;
+            let report = Report::from_lines(
+                stdin.lock().lines().map_while(|line| line.map_err(|err| error = Some(err)).ok()),
+                &options.model
+            );
+
+            if let Some(err) = error {
+                return Err(format!("Unable to read stdin: {}", err));
+            }
+            report?
+        },
+        Some(path) => Report::from_lines(input::read(path)?.lines(), &options.model)?,
+        None => Report::from_lines(
+            Inputs::discover(options.inputs_dir.as_deref()).read(1)?.lines(),
+            &options.model
+        )?,
+    };
+
+    Ok(format_report(&report, options.format))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::args;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(FuelOptions{
+                input: Some(PathBuf::from("-")),
+                inputs_dir: None,
+                format: Format::Csv,
+                model: FuelModel{ cutoff: 3, ..FuelModel::STANDARD },
+            }),
+            parse_args(&args("--input - --format csv --model standard,cutoff=3"))
+        );
+        assert!(parse_args(&args("--format xml")).is_err());
+        assert!(parse_args(&args("--model heavy")).is_err());
+        assert!(parse_args(&args("1")).is_err());
+    }
+
+    #[test]
+    fn test_format_report() {
+        let report = Report::from_lines(vec!["14", "1969"], &FuelModel::STANDARD).unwrap();
+
+        assert_eq!("Fuel: 656\nWith fuel for fuel: 968\n", format_report(&report, Format::Total));
+        assert_eq!(report.csv(), format_report(&report, Format::Csv));
+    }
+
+    #[test]
+    fn test_run() {
+        let options = FuelOptions{
+            input: None,
+            inputs_dir: None,
+            format: Format::Total,
+            model: FuelModel::STANDARD,
+        };
+
+        assert_eq!(Ok("Fuel: 3457681\nWith fuel for fuel: 5183653\n".to_string()), run(&options));
+    }
+}
@@ -0,0 +1,392 @@
+use std::env;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::process;
+
+use common::answers::{Answers, Verdict};
+use common::input::{self, Inputs};
+use common::Day;
+use day1::model::FuelModel;
+
+mod bench;
+mod fuel;
+mod passwords;
+mod wires;
+
+const USAGE: &str = "Usage: aoc <command> [<day|all>] [options]
+
+Commands:
+  run                run a day and check its answers
+  bench              time parsing and each part of a day
+  fuel               report the fuel for each of day 1's modules
+  wires              list or draw the points day 3's wires share
+  passwords          count day 4's passwords that pass chosen rules
+
+Run options:
+  --part PART        run only part 1 or 2
+  --input PATH       read the input from PATH; answers are not checked
+  --inputs-dir DIR   look for day<day>.txt in DIR before $AOC_INPUTS and ./inputs
+  --answers PATH     check against confirmed answers in PATH instead of the
+                     answers.txt beside the inputs
+  --record           store this run's answers as the confirmed ones
+  --model MODEL      run day 1 with another fuel formula (see fuel options);
+                     answers are not checked against the confirmed ones
+
+Bench options:
+  --iterations N     time each stage N times (default 10)
+  --inputs-dir DIR   as for run
+  --baseline PATH    compare against timings in PATH instead of the
+                     bench.txt beside the inputs
+  --save             store this run's timings as the baseline
+
+Fuel options:
+  --input PATH       read module masses from PATH, or - for stdin
+  --inputs-dir DIR   as for run
+  --format FORMAT    table (default), csv or total
+  --model MODEL      fuel formula: a preset (standard, round-up), optionally
+                     followed by overrides, e.g. standard,divisor=4,cutoff=1;
+                     settings are divisor, subtract, rounding (truncate,
+                     floor, ceil, nearest) and cutoff
+
+Wires options:
+  --input PATH       read wires from PATH, one per line
+  --inputs-dir DIR   as for run
+  --at-least K       list points shared by at least K wires (default 2)
+  --pairs            add a table of crossings for each pair of wires
+  --loops            list the points where each wire crosses itself
+  --render FORMAT    draw the wires instead, as ascii or svg
+  --origin X,Y[,Z]   measure distances from this point instead of the central port
+  --top K            rank the K best shared points by --metric
+  --metric METRIC    manhattan (default), chebyshev, euclidean, or steps taken,
+                     optionally weighted by direction, e.g. steps:U=2,NE=3
+
+Passwords options:
+  --input PATH       read the range of passwords from PATH
+  --inputs-dir DIR   as for run
+  --range A-B        count passwords from A to B instead
+  --digits N         passwords have N digits (default 6)
+  --base B           written in base B, from 2 to 36 (default 10)
+  --rule RULE        count the passwords passing RULE; may be repeated, and
+                     defaults to the puzzle's two parts. Tests are
+                     nondecreasing, nonincreasing, and run or sum compared
+                     with >=, = or <=, combined with &, |, ! and brackets,
+                     e.g. 'nondecreasing & run=2'
+  --list             list the passwords passing each rule, a page at a time
+  --page N           show page N of the list (default 1)
+  --per-page N       list N passwords to a page (default 20)
+  --explain PASSWORD say why PASSWORD passes or fails each rule";
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+// The text without its color codes, for output that is not a terminal.
+fn plain(text: &str) -> String {
+    [GREEN, RED, RESET].iter().fold(text.to_string(), |text, code| text.replace(code, ""))
+}
+
+fn colored_if(terminal: bool, text: &str) -> String {
+    if terminal {
+        text.to_string()
+    } else {
+        plain(text)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct RunOptions {
+    days: Vec<u32>,
+    part: Option<u32>,
+    input: Option<PathBuf>,
+    inputs_dir: Option<PathBuf>,
+    answers: Option<PathBuf>,
+    record: bool,
+    model: Option<FuelModel>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    out: String,
+    regressions: usize,
+}
+
+fn solution(day: u32) -> Option<Box<dyn Day>> {
+    match day {
+        1 => Some(Box::new(day1::Day1::default())),
+        2 => Some(Box::new(day2::Day2)),
+        3 => Some(Box::new(day3::Day3)),
+        4 => Some(Box::new(day4::Day4::default())),
+        5 => Some(Box::new(day5::Day5)),
+        _ => None,
+    }
+}
+
+fn solved_days() -> Vec<u32> {
+    (1..=25).filter(|day| solution(*day).is_some()).collect()
+}
+
+fn parse_days(arg: &str) -> Result<Vec<u32>, String> {
+    match arg {
+        "all" => Ok(solved_days()),
+        number => Ok(vec![number.parse().map_err(|_| format!("{} is not a valid day", number))?]),
+    }
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunOptions, String> {
+    let mut days = None;
+    let mut options = RunOptions {
+        days: Vec::new(),
+        part: None,
+        input: None,
+        inputs_dir: None,
+        answers: None,
+        record: false,
+        model: None,
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--part" => {
+                options.part = match value()?.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    other => return Err(format!("{} is not a valid part; expected 1 or 2", other)),
+                }
+            },
+            "--input" => options.input = Some(PathBuf::from(value()?)),
+            "--inputs-dir" => options.inputs_dir = Some(PathBuf::from(value()?)),
+            "--answers" => options.answers = Some(PathBuf::from(value()?)),
+            "--record" => options.record = true,
+            "--model" => options.model = Some(FuelModel::parse(value()?)?),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            day if days.is_none() => days = Some(parse_days(day)?),
+            extra => return Err(format!("Unexpected argument {}", extra)),
+        }
+    }
+
+    options.days = days.ok_or("No day given")?;
+    if options.input.is_some() && options.days.len() > 1 {
+        return Err("--input can only be used when running a single day".to_string());
+    }
+    if options.input.is_some() && options.record {
+        return Err("--record cannot be used with --input".to_string());
+    }
+    if options.model.is_some() {
+        if options.days != [1] {
+            return Err("--model can only be used when running day 1".to_string());
+        }
+        if options.record {
+            return Err("--record cannot be used with --model".to_string());
+        }
+    }
+    Ok(options)
+}
+
+fn run(options: &RunOptions) -> Result<Outcome, String> {
+    let inputs = Inputs::discover(options.inputs_dir.as_deref());
+    let answers_path = options.answers.clone().or_else(|| inputs.answers_path());
+    let mut answers = match &answers_path {
+        Some(path) => Answers::load(path)?,
+        None => Answers::new(),
+    };
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let mut outcome = Outcome{ out: String::new(), regressions: 0 };
+
+    for &day in &options.days {
+        let solution = match (day, options.model) {
+            (1, Some(model)) => Box::new(day1::Day1{ model }),
+            _ => solution(day).ok_or(format!("Day {} has not been solved", day))?,
+        };
+        let input = match &options.input {
+            Some(path) => input::read(path)?,
+            None => inputs.read(day)?,
+        };
+        let results = solution.solve(&input, &parts).map_err(|err| format!("Day {}: {}", day, err))?;
+
+        outcome.out.push_str(&format!("Day {}\n", day));
+        for (&part, answer) in parts.iter().zip(results) {
+            let line = format!("  Part {}: {}", part, answer);
+
+            // Confirmed answers only hold for the standard rules and inputs.
+            let verdict = if options.model.is_some() || options.input.is_some() {
+                Verdict::Unknown
+            } else {
+                answers.check(day, part, &answer)
+            };
+
+            match verdict {
+                Verdict::Unknown => outcome.out.push_str(&format!("{}\n", line)),
+                Verdict::Correct => outcome.out.push_str(&format!("{} {}✓{}\n", line, GREEN, RESET)),
+                Verdict::Regression(expected) => {
+                    outcome.regressions += 1;
+                    outcome.out.push_str(&format!("{}{} (expected {}){}\n", RED, line, expected, RESET));
+                },
+            }
+            if options.record {
+                answers.record(day, part, &answer);
+            }
+        }
+    }
+
+    if options.record {
+        let path = answers_path.ok_or("No inputs directory to store answers in; pass --answers")?;
+
+        answers.save(&path)?;
+        outcome.out.push_str(&format!("Recorded answers in {}\n", path.display()));
+    }
+    Ok(outcome)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(|command| command.as_str()) {
+        Some("run") => parse_run_args(&args[1..]).and_then(|options| run(&options)),
+        Some("bench") => bench::parse_args(&args[1..])
+            .and_then(|options| bench::run(&options))
+            .map(|out| Outcome{ out, regressions: 0 }),
+        Some("fuel") => fuel::parse_args(&args[1..])
+            .and_then(|options| fuel::run(&options))
+            .map(|out| Outcome{ out, regressions: 0 }),
+        Some("wires") => wires::parse_args(&args[1..])
+            .and_then(|options| wires::run(&options))
+            .map(|out| Outcome{ out, regressions: 0 }),
+        Some("passwords") => passwords::parse_args(&args[1..])
+            .and_then(|options| passwords::run(&options))
+            .map(|out| Outcome{ out, regressions: 0 }),
+        Some("--help") | Some("-h") => Ok(Outcome{ out: format!("{}\n", USAGE), regressions: 0 }),
+        Some(command) => Err(format!("Unknown command {}", command)),
+        None => Err("No command given".to_string()),
+    };
+
+    match result {
+        Ok(outcome) => {
+            print!("{}", colored_if(io::stdout().is_terminal(), &outcome.out));
+            if outcome.regressions > 0 {
+                eprintln!("{}", colored_if(
+                    io::stderr().is_terminal(),
+                    &format!("{}{} answer(s) differ from the confirmed ones{}", RED, outcome.regressions, RESET)
+                ));
+                process::exit(2);
+            }
+        },
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            process::exit(1);
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    // Splits a command line on whitespace, for the command modules' tests
+    // as well as these.
+    pub fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    fn options(days: Vec<u32>) -> RunOptions {
+        RunOptions{ days, part: None, input: None, inputs_dir: None, answers: None, record: false, model: None }
+    }
+
+    #[test]
+    fn test_parse_run_args() {
+        assert_eq!(
+            Ok(RunOptions{
+                part: Some(2),
+                input: Some(PathBuf::from("wires.txt")),
+                inputs_dir: Some(PathBuf::from("mine")),
+                ..options(vec![3])
+            }),
+            parse_run_args(&args("3 --part 2 --input wires.txt --inputs-dir mine"))
+        );
+        assert_eq!(Ok(options(vec![1])), parse_run_args(&args("1")));
+        assert_eq!(
+            Ok(RunOptions{ answers: Some(PathBuf::from("a.txt")), record: true, ..options(solved_days()) }),
+            parse_run_args(&args("all --answers a.txt --record"))
+        );
+        assert!(parse_run_args(&args("")).is_err());
+        assert!(parse_run_args(&args("x")).is_err());
+        assert!(parse_run_args(&args("1 --part 3")).is_err());
+        assert!(parse_run_args(&args("1 2")).is_err());
+        assert!(parse_run_args(&args("all --input day1.txt")).is_err());
+        assert!(parse_run_args(&args("4 --input day4.txt --record")).is_err());
+        assert!(parse_run_args(&args("2 --model round-up")).is_err());
+        assert!(parse_run_args(&args("1 --model round-up --record")).is_err());
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(Ok(vec![3]), parse_days("3"));
+        assert_eq!(Ok(solved_days()), parse_days("all"));
+        assert!(parse_days("three").is_err());
+    }
+
+    #[test]
+    fn test_solution() {
+        assert_eq!(vec![1, 2, 3, 4, 5], solved_days());
+        assert!(solution(0).is_none());
+        assert!(solution(26).is_none());
+    }
+
+    #[test]
+    fn test_run() {
+        assert!(run(&options(vec![26])).is_err());
+        assert!(run(&RunOptions{ input: Some(PathBuf::from("missing.txt")), ..options(vec![1]) }).is_err());
+    }
+
+    #[test]
+    fn test_run_input() {
+        let path = env::temp_dir().join(format!("aoc-input-{}.txt", process::id()));
+
+        fs::write(&path, "100000-200000\n").unwrap();
+        let outcome = run(&RunOptions{ input: Some(path.clone()), ..options(vec![4]) }).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!("Day 4\n  Part 1: 1231\n  Part 2: 898\n", outcome.out);
+        assert_eq!(0, outcome.regressions);
+    }
+
+    #[test]
+    fn test_plain() {
+        assert_eq!("  Part 1: 1 ✓\n", plain(&format!("  Part 1: 1 {}✓{}\n", GREEN, RESET)));
+        assert_eq!("red", colored_if(false, &format!("{}red{}", RED, RESET)));
+        assert_eq!(format!("{}red{}", RED, RESET), colored_if(true, &format!("{}red{}", RED, RESET)));
+    }
+
+    #[test]
+    fn test_run_model() {
+        let options = parse_run_args(&args("1 --model round-up")).unwrap();
+
+        assert_eq!("Day 1\n  Part 1: 3457748\n  Part 2: 5184491\n", run(&options).unwrap().out);
+    }
+
+    #[test]
+    fn test_run_answers() {
+        let path = env::temp_dir().join(format!("aoc-answers-{}.txt", process::id()));
+
+        fs::write(&path, "4 1 1864\n4 2 1\n").unwrap();
+        let outcome = run(&RunOptions{ answers: Some(path.clone()), ..options(vec![4]) }).unwrap();
+        assert_eq!(
+            format!(
+                "Day 4\n  Part 1: 1864 {}✓{}\n{}  Part 2: 1258 (expected 1){}\n",
+                GREEN, RESET, RED, RESET
+            ),
+            outcome.out
+        );
+        assert_eq!(1, outcome.regressions);
+
+        run(&RunOptions{ answers: Some(path.clone()), record: true, ..options(vec![4]) }).unwrap();
+        assert_eq!(Some("1258"), Answers::load(&path).unwrap().get(4, 2));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,205 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use common::input::{self, Inputs};
+use day4::rules::Rule;
+use day4::Format;
+
+#[derive(Debug, PartialEq)]
+pub struct PasswordsOptions {
+    input: Option<PathBuf>,
+    inputs_dir: Option<PathBuf>,
+    range: Option<String>,
+    digits: u32,
+    base: u32,
+    rules: Vec<Rule>,
+    list: bool,
+    page: usize,
+    per_page: usize,
+    explain: Option<String>,
+}
+
+pub fn parse_args(args: &[String]) -> Result<PasswordsOptions, String> {
+    let mut options = PasswordsOptions{
+        input: None,
+        inputs_dir: None,
+        range: None,
+        digits: Format::STANDARD.digits,
+        base: Format::STANDARD.base,
+        rules: Vec::new(),
+        list: false,
+        page: 1,
+        per_page: 20,
+        explain: None,
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--input" => options.input = Some(PathBuf::from(value()?)),
+            "--inputs-dir" => options.inputs_dir = Some(PathBuf::from(value()?)),
+            "--range" => options.range = Some(value()?.to_string()),
+            "--digits" => {
+                let digits = value()?;
+                options.digits = digits.parse().map_err(|_| format!("{} is not a valid number of digits", digits))?;
+            },
+            "--base" => {
+                let base = value()?;
+                options.base = base.parse().map_err(|_| format!("{} is not a valid base", base))?;
+            },
+            "--rule" => options.rules.push(Rule::parse(value()?)?),
+            "--list" => options.list = true,
+            "--page" => {
+                let page = value()?;
+                options.page = match page.parse() {
+                    Ok(page) if page > 0 => page,
+                    _ => return Err(format!("{} is not a valid page; pages count from 1", page)),
+                }
+            },
+            "--per-page" => {
+                let per_page = value()?;
+                options.per_page = match per_page.parse() {
+                    Ok(per_page) if per_page > 0 => per_page,
+                    _ => return Err(format!("{} is not a valid page size", per_page)),
+                }
+            },
+            "--explain" => options.explain = Some(value()?.to_string()),
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+
+    if options.explain.is_some() && (options.list || options.range.is_some() || options.input.is_some()) {
+        return Err("--explain checks a single password, so takes no range or --list".to_string());
+    }
+    if options.input.is_some() && options.range.is_some() {
+        return Err("--input and --range cannot be used together".to_string());
+    }
+    // Without rules of its own, count both parts of the puzzle.
+    if options.rules.is_empty() {
+        options.rules = vec![Rule::part1(), Rule::part2()];
+    }
+    Ok(options)
+}
+
+// One page of the passwords passing each rule, with their count. A page
+// past the last one lists nothing.
+fn list(format: &Format, range: &RangeInclusive<u128>, options: &PasswordsOptions) -> String {
+    let mut out = String::new();
+
+    for rule in &options.rules {
+        let count = format.count(range, rule);
+        let pages = count.div_ceil(options.per_page).max(1);
+
+        out.push_str(&format!("{}: {} (page {} of {})\n", rule, count, options.page, pages));
+        if options.page > pages {
+            continue;
+        }
+        for pw in format.page(range, rule, (options.page - 1) * options.per_page, options.per_page) {
+            out.push_str(&format!("  {}\n", format.write(pw)));
+        }
+    }
+    out
+}
+
+pub fn run(options: &PasswordsOptions) -> Result<String, String> {
+    let format = Format::new(options.digits, options.base)?;
+
+    if let Some(pw) = &options.explain {
+        let pw = format.parse_password(pw)?;
+
+        return Ok(options.rules.iter().map(|rule| format.explain(pw, rule).to_string()).collect());
+    }
+
+    let range = match (&options.range, &options.input) {
+        (Some(range), _) => format.parse_range(range)?,
+        (None, Some(path)) => format.parse_range(&input::read(path)?)?,
+        (None, None) => format.parse_range(&Inputs::discover(options.inputs_dir.as_deref()).read(4)?)?,
+    };
+
+    if options.list {
+        return Ok(list(&format, &range, options));
+    }
+    Ok(options.rules.iter()
+        .map(|rule| format!("{}: {}\n", rule, format.count(&range, rule)))
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::args;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(PasswordsOptions{
+                input: None,
+                inputs_dir: None,
+                range: Some("00-ff".to_string()),
+                digits: 2,
+                base: 16,
+                rules: vec![Rule::NonIncreasing, Rule::SumAtLeast(20)],
+                list: false,
+                page: 1,
+                per_page: 20,
+                explain: None,
+            }),
+            parse_args(&args("--range 00-ff --digits 2 --base 16 --rule nonincreasing --rule sum>=20"))
+        );
+        assert_eq!(vec![Rule::part1(), Rule::part2()], parse_args(&[]).unwrap().rules);
+        assert!(parse_args(&args("--rule run")).is_err());
+        assert!(parse_args(&args("--digits six")).is_err());
+        assert!(parse_args(&args("--input day4.txt --range 1-2")).is_err());
+        assert!(parse_args(&args("4")).is_err());
+        assert!(parse_args(&args("--page 0")).is_err());
+        assert!(parse_args(&args("--explain 111111 --list")).is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(
+            Ok("nondecreasing & run>=2: 1864\nnondecreasing & run=2: 1258\n".to_string()),
+            run(&parse_args(&[]).unwrap())
+        );
+        assert_eq!(
+            Ok("sum=3: 4\n".to_string()),
+            run(&parse_args(&args("--range 0-99 --digits 2 --rule sum=3")).unwrap())
+        );
+        assert!(run(&parse_args(&args("--range 0-ff --digits 2")).unwrap()).is_err());
+        assert!(run(&parse_args(&args("--base 40")).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_list() {
+        let options = parse_args(&args("--range 0-99 --digits 2 --list --page 2 --per-page 4 --rule nondecreasing&run>=2")).unwrap();
+
+        assert_eq!(Ok("nondecreasing & run>=2: 10 (page 2 of 3)\n  44\n  55\n  66\n  77\n".to_string()), run(&options));
+
+        let options = parse_args(&args("--range 0-9 --digits 2 --list --page 3 --rule sum=20")).unwrap();
+        assert_eq!(Ok("sum=20: 0 (page 3 of 1)\n".to_string()), run(&options));
+
+        let page = usize::MAX.to_string();
+        let options = parse_args(&args(&format!("--list --range 0-99 --digits 2 --page {} --per-page 100", page))).unwrap();
+        assert_eq!(
+            Ok(format!("nondecreasing & run>=2: 10 (page {0} of 1)\nnondecreasing & run=2: 10 (page {0} of 1)\n", page)),
+            run(&options)
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let options = parse_args(&args("--explain 123444 --rule nondecreasing&run=2")).unwrap();
+
+        assert_eq!(
+            Ok("\
+fail nondecreasing & run=2: 1 of 2 pass
+  pass nondecreasing: digits never decrease
+  fail run=2: run of 3 at positions 4-6 but no run of exactly 2
+".to_string()),
+            run(&options)
+        );
+        assert!(run(&parse_args(&args("--explain 1234567")).unwrap()).is_err());
+    }
+}
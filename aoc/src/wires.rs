@@ -0,0 +1,289 @@
+use std::path::PathBuf;
+
+use common::input::{self, Inputs};
+use common::Solution;
+use day3::index::WireIndex;
+use day3::metric::Metric;
+use day3::render;
+use day3::wire::{fewest_steps, half_step_crossings, nearest, pairs, pairwise, shared_points, Wire};
+use day3::parse::MAX_LENGTH;
+use day3::{parse_wires, Day3, Point};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Render {
+    Ascii,
+    Svg,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct WiresOptions {
+    input: Option<PathBuf>,
+    inputs_dir: Option<PathBuf>,
+    at_least: usize,
+    pairs: bool,
+    loops: bool,
+    render: Option<Render>,
+    origin: Point,
+    metric: Metric,
+    top: usize,
+}
+
+pub fn parse_args(args: &[String]) -> Result<WiresOptions, String> {
+    let mut options = WiresOptions{
+        input: None,
+        inputs_dir: None,
+        at_least: 2,
+        pairs: false,
+        loops: false,
+        render: None,
+        origin: Point::new(0, 0),
+        metric: Metric::Manhattan,
+        top: 0,
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--input" => options.input = Some(PathBuf::from(value()?)),
+            "--inputs-dir" => options.inputs_dir = Some(PathBuf::from(value()?)),
+            "--at-least" => {
+                let at_least = value()?;
+                options.at_least = match at_least.parse() {
+                    Ok(at_least) if at_least >= 2 => at_least,
+                    _ => return Err(format!("{} is not a valid number of wires; expected 2 or more", at_least)),
+                }
+            },
+            "--pairs" => options.pairs = true,
+            "--loops" => options.loops = true,
+            "--render" => {
+                options.render = match value()?.as_str() {
+                    "ascii" => Some(Render::Ascii),
+                    "svg" => Some(Render::Svg),
+                    other => return Err(format!("Unknown rendering {}", other)),
+                }
+            },
+            "--origin" => options.origin = parse_point(value()?)?,
+            "--metric" => options.metric = Metric::parse(value()?)?,
+            "--top" => {
+                let top = value()?;
+                options.top = top.parse().map_err(|_| format!("{} is not a valid number of crossings", top))?;
+            },
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+    Ok(options)
+}
+
+// Coordinates are held to the same bound as wire lengths, so distances
+// from the point can't overflow.
+fn parse_point(s: &str) -> Result<Point, String> {
+    let coordinates: Vec<i32> = s.split(',').map(|part| part.trim().parse()).collect::<Result<_, _>>()
+        .map_err(|_| format!("{} is not a valid point; expected x,y or x,y,z", s))?;
+
+    if coordinates.iter().any(|coordinate| !(-MAX_LENGTH..=MAX_LENGTH).contains(coordinate)) {
+        return Err(format!("{} is too far out; coordinates must be within {}", s, MAX_LENGTH));
+    }
+
+    match coordinates.as_slice() {
+        [x, y] => Ok(Point::new(*x, *y)),
+        [x, y, z] => Ok(Point::new_3d(*x, *y, *z)),
+        _ => Err(format!("{} is not a valid point; expected x,y or x,y,z", s)),
+    }
+}
+
+// Whole distances print as they are, others to three places.
+fn format_measure(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value)
+    } else {
+        format!("{:.3}", value)
+    }
+}
+
+fn or_none(value: Option<i32>) -> String {
+    value.map_or("-".to_string(), |value| value.to_string())
+}
+
+// Wires are numbered from 1, as the lines of the input are.
+fn report(wires: &[Wire], options: &WiresOptions) -> String {
+    let origin = options.origin;
+    let shared = shared_points(wires, options.at_least);
+    let mut out = format!("Points shared by at least {} of {} wires:\n", options.at_least, wires.len());
+
+    for shared in &shared {
+        let passing: Vec<String> = shared.steps.iter()
+            .map(|(wire, steps)| format!("wire {} at {} steps", wire + 1, steps))
+            .collect();
+
+        out.push_str(&format!("  {}: {}\n", shared.point, passing.join(", ")));
+    }
+    for (i, j) in pairs(wires.len()) {
+        for half in half_step_crossings(&wires[i], &wires[j]) {
+            out.push_str(&format!(
+                "  Wires {} and {} cross between grid points at {}, after {} and {} steps\n",
+                i + 1, j + 1, half, f64::from(half.steps.0) / 2.0, f64::from(half.steps.1) / 2.0
+            ));
+        }
+    }
+    out.push_str(&format!("Nearest: {}\n", or_none(nearest(shared.iter().map(|shared| &shared.point), &origin))));
+    out.push_str(&format!("Fewest steps: {}\n", or_none(fewest_steps(&shared))));
+
+    if options.top > 0 {
+        out.push_str(&format!("\nBest {} by {} from {}:\n", options.top, options.metric, origin));
+        for (rank, (value, shared)) in options.metric.rank(&origin, &shared, wires, options.top).iter().enumerate() {
+            out.push_str(&format!("  {}. {}: {}\n", rank + 1, shared.point, format_measure(*value)));
+        }
+    }
+
+    if options.pairs {
+        out.push_str(&format!("\n{:<8}{:>10}{:>10}{:>14}\n", "wires", "crossings", "nearest", "fewest steps"));
+        for ((i, j), found) in pairwise(wires) {
+            out.push_str(&format!(
+                "{:<8}{:>10}{:>10}{:>14}\n",
+                format!("{}-{}", i + 1, j + 1),
+                found.len(),
+                or_none(nearest(found.iter().map(|crossing| &crossing.point), &origin)),
+                or_none(found.iter().map(|crossing| crossing.total_steps()).min())
+            ));
+        }
+    }
+
+    if options.loops {
+        for (number, wire) in wires.iter().enumerate() {
+            let index = WireIndex::new(wire);
+
+            out.push_str(&format!("\nWire {} crosses itself {} time(s):\n", number + 1, index.loops().len()));
+            for found in index.loops() {
+                out.push_str(&format!(
+                    "  {}: first at {} steps, again at {} steps (loop of {})\n",
+                    found.point, found.entry, found.reentry, found.length()
+                ));
+            }
+        }
+    }
+    out
+}
+
+pub fn run(options: &WiresOptions) -> Result<String, String> {
+    let text = match &options.input {
+        Some(path) => input::read(path)?,
+        None => Inputs::discover(options.inputs_dir.as_deref()).read(3)?,
+    };
+
+    match options.render {
+        Some(Render::Ascii) => render::ascii(&parse_wires(&text)?),
+        Some(Render::Svg) => Ok(render::svg(&parse_wires(&text)?)),
+        None => Ok(report(&Day3.parse(&text)?, options)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::args;
+
+    fn options() -> WiresOptions {
+        parse_args(&[]).unwrap()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(WiresOptions{
+                input: Some(PathBuf::from("w.txt")),
+                inputs_dir: None,
+                at_least: 3,
+                pairs: true,
+                loops: true,
+                render: Some(Render::Svg),
+                origin: Point::new(-2, 5),
+                metric: Metric::Euclidean,
+                top: 3,
+            }),
+            parse_args(&args("--input w.txt --at-least 3 --pairs --loops --render svg --origin -2,5 --metric euclidean --top 3"))
+        );
+        assert!(parse_args(&args("--origin 1")).is_err());
+        assert_eq!(Ok(Point::new_3d(1, 2, 3)), parse_args(&args("--origin 1,2,3")).map(|options| options.origin));
+        assert!(parse_args(&args("--origin 1,2,3,4")).is_err());
+        assert!(parse_args(&args("--origin 0,2147483647")).is_err());
+        assert!(parse_args(&args("--origin -2147483648,0")).is_err());
+        assert!(parse_args(&args("--metric taxicab")).is_err());
+        assert!(parse_args(&args("--render png")).is_err());
+        assert!(parse_args(&args("--at-least 1")).is_err());
+        assert!(parse_args(&args("3")).is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let wires = Day3.parse("R8,U5,L5,D3\nU7,R6,D4,L4\nU3,R8").unwrap();
+        let options = WiresOptions{ at_least: 3, pairs: true, ..options() };
+
+        assert_eq!(
+            "Points shared by at least 3 of 3 wires:
+  (3, 3): wire 1 at 20 steps, wire 2 at 20 steps, wire 3 at 6 steps
+Nearest: 6
+Fewest steps: 46
+
+wires    crossings   nearest  fewest steps
+1-2              2         6            30
+1-3              2         6            22
+2-3              8         1             2
+",
+            report(&wires, &options)
+        );
+    }
+
+    #[test]
+    fn test_report_half_steps() {
+        let wires = Day3.parse("NE3\nU5,SE5").unwrap();
+
+        assert_eq!(
+            "Points shared by at least 2 of 2 wires:
+  Wires 1 and 2 cross between grid points at (2.5, 2.5), after 2.5 and 7.5 steps
+Nearest: -
+Fewest steps: -
+",
+            report(&wires, &options())
+        );
+    }
+
+    #[test]
+    fn test_report_ranked() {
+        let wires = Day3.parse("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+        let options = WiresOptions{ origin: Point::new(1, 1), metric: Metric::Euclidean, top: 5, ..options() };
+
+        assert!(report(&wires, &options).ends_with(
+            "Nearest: 4
+Fewest steps: 30
+
+Best 5 by euclidean from (1, 1):
+  1. (3, 3): 2.828
+  2. (6, 5): 6.403
+"
+        ));
+    }
+
+    #[test]
+    fn test_report_loops() {
+        let wires = Day3.parse("R2,U2,L1,D3\nU1").unwrap();
+        let options = WiresOptions{ loops: true, ..options() };
+
+        assert!(report(&wires, &options).ends_with(
+            "
+Wire 1 crosses itself 1 time(s):
+  (1, 0): first at 1 steps, again at 7 steps (loop of 6)
+
+Wire 2 crosses itself 0 time(s):
+"
+        ));
+    }
+
+    #[test]
+    fn test_run() {
+        assert!(run(&options()).unwrap().ends_with("Nearest: 709\nFewest steps: 13836\n"));
+        assert!(run(&WiresOptions{ render: Some(Render::Ascii), ..options() }).is_err());
+        assert!(run(&WiresOptions{ render: Some(Render::Svg), ..options() }).unwrap().starts_with("<svg "));
+    }
+}
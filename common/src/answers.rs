@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use super::Result;
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    // No confirmed answer has been recorded for this part.
+    Unknown,
+    Correct,
+    Regression(String),
+}
+
+// Confirmed answers, one `day part answer` line each, keyed by day and part.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u32, u32), String>,
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        Answers{ entries: BTreeMap::new() }
+    }
+
+    pub fn parse(s: &str) -> Result<Answers> {
+        let mut answers = Answers::new();
+
+        for (number, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.splitn(3, ' ').collect();
+            match fields.as_slice() {
+                [day, part, answer] => match (day.parse(), part.parse()) {
+                    (Ok(day), Ok(part)) => answers.record(day, part, answer.trim()),
+                    _ => return Err(format!("Line {}: {} is not a valid day and part", number + 1, line)),
+                },
+                _ => return Err(format!("Line {}: expected `day part answer` but found {}", number + 1, line)),
+            }
+        }
+        Ok(answers)
+    }
+
+    // A missing file is an empty store, so the first --record creates it.
+    pub fn load(path: &Path) -> Result<Answers> {
+        if !path.exists() {
+            return Ok(Answers::new());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read {}: {}", path.display(), err))?;
+
+        Answers::parse(&content).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())
+            .map_err(|err| format!("Unable to write {}: {}", path.display(), err))
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.entries.get(&(day, part)).map(|answer| answer.as_str())
+    }
+
+    pub fn record(&mut self, day: u32, part: u32, answer: &str) {
+        self.entries.insert((day, part), answer.to_string());
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Regression(expected.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day part answer")?;
+        for ((day, part), answer) in &self.entries {
+            writeln!(f, "{} {} {}", day, part, answer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("# day part answer\n1 1 3457681\n\n2 2 No noun and verb found\n").unwrap();
+
+        assert_eq!(Some("3457681"), answers.get(1, 1));
+        assert_eq!(Some("No noun and verb found"), answers.get(2, 2));
+        assert_eq!(None, answers.get(1, 2));
+
+        assert!(Answers::parse("1 1").is_err());
+        assert!(Answers::parse("one 1 2").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::new();
+        answers.record(3, 2, "13836");
+        answers.record(3, 1, "709");
+
+        assert_eq!("# day part answer\n3 1 709\n3 2 13836\n", answers.to_string());
+        assert_eq!(Ok(answers), Answers::parse("3 2 13836\n3 1 709\n"));
+    }
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::new();
+        answers.record(4, 1, "1864");
+
+        assert_eq!(Verdict::Correct, answers.check(4, 1, "1864"));
+        assert_eq!(Verdict::Regression("1864".to_string()), answers.check(4, 1, "1865"));
+        assert_eq!(Verdict::Unknown, answers.check(4, 2, "1258"));
+    }
+
+    #[test]
+    fn test_load_missing() {
+        assert_eq!(Ok(Answers::new()), Answers::load(Path::new("does-not-exist/answers.txt")));
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::Result;
+
+pub const BASELINE_FILE: &str = "bench.txt";
+
+pub const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "No samples to summarize");
+        samples.sort();
+
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+// Time `iterations` calls of `f`.
+pub fn measure<F: FnMut()>(iterations: usize, mut f: F) -> Stats {
+    Stats::from_samples(
+        (0..iterations.max(1)).map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        }).collect()
+    )
+}
+
+// Timings for each stage of a day, in the order of STAGES.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl Timings {
+    pub fn stages(&self) -> [(&'static str, Stats); 3] {
+        [(STAGES[0], self.parse), (STAGES[1], self.part1), (STAGES[2], self.part2)]
+    }
+}
+
+// Saved timings to compare a run against, one `day stage min median max`
+// line each with durations in nanoseconds.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    entries: BTreeMap<(u32, String), Stats>,
+}
+
+impl Baseline {
+    pub fn new() -> Baseline {
+        Baseline{ entries: BTreeMap::new() }
+    }
+
+    pub fn parse(s: &str) -> Result<Baseline> {
+        let mut baseline = Baseline::new();
+
+        for (number, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let nanos: Vec<u64> = fields.iter().skip(2).filter_map(|field| field.parse().ok()).collect();
+
+            match (fields.as_slice(), fields[0].parse(), nanos.as_slice()) {
+                ([_, stage, _, _, _], Ok(day), [min, median, max]) => baseline.record(day, stage, Stats {
+                    min: Duration::from_nanos(*min),
+                    median: Duration::from_nanos(*median),
+                    max: Duration::from_nanos(*max),
+                }),
+                _ => return Err(format!(
+                    "Line {}: expected `day stage min median max` but found {}",
+                    number + 1,
+                    line
+                )),
+            }
+        }
+        Ok(baseline)
+    }
+
+    pub fn load(path: &Path) -> Result<Baseline> {
+        if !path.exists() {
+            return Ok(Baseline::new());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read {}: {}", path.display(), err))?;
+
+        Baseline::parse(&content).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())
+            .map_err(|err| format!("Unable to write {}: {}", path.display(), err))
+    }
+
+    pub fn get(&self, day: u32, stage: &str) -> Option<&Stats> {
+        self.entries.get(&(day, stage.to_string()))
+    }
+
+    pub fn record(&mut self, day: u32, stage: &str, stats: Stats) {
+        self.entries.insert((day, stage.to_string()), stats);
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day stage min median max (ns)")?;
+        for ((day, stage), stats) in &self.entries {
+            writeln!(
+                f,
+                "{} {} {} {} {}",
+                day,
+                stage,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.max.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(min: u64, median: u64, max: u64) -> Stats {
+        Stats {
+            min: Duration::from_millis(min),
+            median: Duration::from_millis(median),
+            max: Duration::from_millis(max),
+        }
+    }
+
+    #[test]
+    fn test_from_samples() {
+        let samples = [5, 1, 3, 2, 4].iter().map(|ms| Duration::from_millis(*ms)).collect();
+
+        assert_eq!(millis(1, 3, 5), Stats::from_samples(samples));
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        measure(4, || calls += 1);
+        assert_eq!(4, calls);
+
+        measure(0, || calls += 1);
+        assert_eq!(5, calls);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let mut baseline = Baseline::new();
+        baseline.record(4, "part1", millis(1, 2, 3));
+        baseline.record(1, "parse", millis(0, 0, 1));
+
+        let saved = baseline.to_string();
+        assert_eq!(
+            "# day stage min median max (ns)\n1 parse 0 0 1000000\n4 part1 1000000 2000000 3000000\n",
+            saved
+        );
+        assert_eq!(Ok(baseline), Baseline::parse(&saved));
+    }
+
+    #[test]
+    fn test_baseline_parse_errors() {
+        assert!(Baseline::parse("1 parse 1 2").is_err());
+        assert!(Baseline::parse("x parse 1 2 3").is_err());
+        assert!(Baseline::parse("1 parse 1 2 z").is_err());
+    }
+}
@@ -0,0 +1,172 @@
+// Digits of unsigned numbers in any base from 2 up. Numbers are taken as
+// u128 so that u64 and smaller convert without loss.
+
+// The digits of a number, least significant first, or most significant
+// first when reversed. Nothing is allocated.
+#[derive(Clone, Debug)]
+pub struct Digits {
+    num: u128,
+    base: u128,
+    len: u32,
+}
+
+impl Iterator for Digits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.num % self.base;
+
+        self.num /= self.base;
+        self.len -= 1;
+        Some(digit as u32)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl DoubleEndedIterator for Digits {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+
+        // Padding past the largest power that fits is all zeros.
+        let place = match self.base.checked_pow(self.len) {
+            Some(place) => place,
+            None => return Some(0),
+        };
+        let digit = self.num / place;
+
+        self.num %= place;
+        Some(digit as u32)
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+fn check_base(base: u32) -> u128 {
+    assert!(base >= 2, "{} is not a valid base", base);
+    u128::from(base)
+}
+
+// How many digits the number has; zero has one.
+pub fn len<N: Into<u128>>(num: N, base: u32) -> u32 {
+    let base = check_base(base);
+    let mut num = num.into();
+    let mut len = 1;
+
+    while num >= base {
+        num /= base;
+        len += 1;
+    }
+    len
+}
+
+pub fn digits<N: Into<u128>>(num: N, base: u32) -> Digits {
+    let num = num.into();
+
+    Digits{ num, base: check_base(base), len: len(num, base) }
+}
+
+// As `digits`, but with leading zeros to make up at least `width` digits.
+pub fn padded<N: Into<u128>>(num: N, base: u32, width: u32) -> Digits {
+    let num = num.into();
+
+    Digits{ num, base: check_base(base), len: len(num, base).max(width) }
+}
+
+// The `n`th digit counting up from the right, from 0. Digits past the
+// number's own are zero.
+pub fn nth<N: Into<u128>>(num: N, n: u32, base: u32) -> u32 {
+    match check_base(base).checked_pow(n) {
+        Some(place) => (num.into() / place % u128::from(base)) as u32,
+        None => 0,
+    }
+}
+
+// The number with these digits, most significant first, if it fits.
+pub fn from_digits<I: IntoIterator<Item = u32>>(digits: I, base: u32) -> Option<u128> {
+    let base = check_base(base);
+
+    digits.into_iter().try_fold(0u128, |num, digit| {
+        if u128::from(digit) >= base {
+            return None;
+        }
+        num.checked_mul(base)?.checked_add(u128::from(digit))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Lcg;
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(vec![6, 5, 4, 3, 2, 1], digits(123456u32, 10).collect::<Vec<u32>>());
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], digits(123456u32, 10).rev().collect::<Vec<u32>>());
+        assert_eq!(vec![0xa, 0xb, 0xc], digits(0xabcu64, 16).rev().collect::<Vec<u32>>());
+        assert_eq!(vec![0], digits(0u8, 10).collect::<Vec<u32>>());
+        assert_eq!(39, digits(u128::MAX, 10).len());
+        assert_eq!(vec![0, 0, 4, 2], padded(42u8, 10, 4).rev().collect::<Vec<u32>>());
+        assert_eq!(vec![1, 2, 3], padded(123u8, 10, 2).rev().collect::<Vec<u32>>());
+
+        // Both ends at once meet in the middle.
+        let mut both = digits(12345u32, 10);
+        assert_eq!((Some(5), Some(1)), (both.next(), both.next_back()));
+        assert_eq!(vec![4, 3, 2], both.collect::<Vec<u32>>());
+
+        let mut wide = padded(7u8, 2, 200).rev();
+        assert_eq!(Some(0), wide.next());
+        assert_eq!(vec![1, 1, 1], wide.skip(196).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(1, len(0u8, 10));
+        assert_eq!(10, len(1234567890u64, 10));
+        assert_eq!(128, len(u128::MAX, 2));
+        assert_eq!(16, len(u64::MAX, 16));
+    }
+
+    #[test]
+    fn test_nth() {
+        assert_eq!(6, nth(123456u32, 0, 10));
+        assert_eq!(4, nth(654321u32, 3, 10));
+        assert_eq!(8, nth(892109u32, 5, 10));
+        assert_eq!(0xc, nth(0xabcdefu32, 3, 16));
+        assert_eq!(1, nth(12345678901u64, 10, 10));
+        assert_eq!(0, nth(12345678901u64, 11, 10));
+        assert_eq!(0, nth(u128::MAX, 200, 10));
+    }
+
+    #[test]
+    fn test_from_digits() {
+        assert_eq!(Some(123456), from_digits(vec![1, 2, 3, 4, 5, 6], 10));
+        assert_eq!(Some(0xff), from_digits(vec![15, 15], 16));
+        assert_eq!(Some(0), from_digits(Vec::new(), 10));
+        assert_eq!(None, from_digits(vec![1, 10], 10));
+        assert_eq!(Some(u128::MAX), from_digits(digits(u128::MAX, 7).rev(), 7));
+        assert_eq!(None, from_digits(padded(u128::MAX, 2, 129).rev().map(|_| 1), 2));
+
+        let mut random = Lcg::new(50);
+        for _ in 0..100 {
+            let seed = random.next_u64();
+            let base = 2 + (seed >> 58) as u32;
+
+            assert_eq!(Some(u128::from(seed)), from_digits(digits(seed, base).rev(), base));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_base_one() {
+        digits(5u8, 1);
+    }
+}
@@ -0,0 +1,125 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::answers::ANSWERS_FILE;
+use super::Result;
+
+// Environment variable naming a directory of dayN.txt inputs.
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+
+// The inputs directory checked into the workspace, so the runner finds it
+// regardless of the current directory.
+const WORKSPACE_INPUTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs");
+
+pub fn file_name(day: u32) -> String {
+    format!("day{}.txt", day)
+}
+
+pub fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|err| format!("Unable to read {}: {}", path.display(), err))
+}
+
+// The directories searched, in order, for a day's input.
+#[derive(Debug, Default, PartialEq)]
+pub struct Inputs {
+    dirs: Vec<PathBuf>,
+}
+
+impl Inputs {
+    pub fn new() -> Inputs {
+        Inputs{ dirs: Vec::new() }
+    }
+
+    // Search `dir` if given, then $AOC_INPUTS, then ./inputs and finally
+    // the workspace's own inputs directory.
+    pub fn discover(dir: Option<&Path>) -> Inputs {
+        let mut inputs = Inputs::new();
+
+        if let Some(dir) = dir {
+            inputs.push(dir);
+        }
+        if let Some(dir) = env::var_os(INPUTS_ENV) {
+            inputs.push(dir);
+        }
+        inputs.push("inputs");
+        inputs.push(WORKSPACE_INPUTS);
+        inputs
+    }
+
+    pub fn push<P: Into<PathBuf>>(&mut self, dir: P) {
+        self.dirs.push(dir.into());
+    }
+
+    pub fn path(&self, day: u32) -> Result<PathBuf> {
+        let candidates: Vec<PathBuf> = self.dirs.iter().map(|dir| dir.join(file_name(day))).collect();
+
+        match candidates.iter().find(|path| path.is_file()) {
+            Some(path) => Ok(path.clone()),
+            None => Err(format!(
+                "No input found for day {}; looked for {}. Pass --input or --inputs-dir, or set {}",
+                day,
+                candidates.iter().map(|path| path.display().to_string()).collect::<Vec<String>>().join(", "),
+                INPUTS_ENV
+            )),
+        }
+    }
+
+    pub fn read(&self, day: u32) -> Result<String> {
+        read(&self.path(day)?)
+    }
+
+    // Local records such as confirmed answers are kept beside the inputs,
+    // in the first searched directory that exists.
+    pub fn local_path(&self, file_name: &str) -> Option<PathBuf> {
+        self.dirs.iter().find(|dir| dir.is_dir()).map(|dir| dir.join(file_name))
+    }
+
+    pub fn answers_path(&self) -> Option<PathBuf> {
+        self.local_path(ANSWERS_FILE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let mut inputs = Inputs::new();
+        inputs.push("does-not-exist");
+        inputs.push(WORKSPACE_INPUTS);
+
+        assert_eq!(Path::new(WORKSPACE_INPUTS).join("day1.txt"), inputs.path(1).unwrap());
+        assert!(inputs.read(1).unwrap().lines().count() > 0);
+    }
+
+    #[test]
+    fn test_path_missing() {
+        let mut inputs = Inputs::new();
+        inputs.push("does-not-exist");
+
+        let err = inputs.path(26).unwrap_err();
+        assert!(err.contains("day 26"));
+        assert!(err.contains("does-not-exist/day26.txt"));
+        assert!(err.contains(INPUTS_ENV));
+    }
+
+    #[test]
+    fn test_answers_path() {
+        let mut inputs = Inputs::new();
+        assert_eq!(None, inputs.answers_path());
+
+        inputs.push("does-not-exist");
+        inputs.push(WORKSPACE_INPUTS);
+        assert_eq!(Some(Path::new(WORKSPACE_INPUTS).join(ANSWERS_FILE)), inputs.answers_path());
+    }
+
+    #[test]
+    fn test_discover() {
+        let inputs = Inputs::discover(Some(Path::new("mine")));
+
+        assert_eq!(PathBuf::from("mine"), inputs.dirs[0]);
+        assert_eq!(&PathBuf::from(WORKSPACE_INPUTS), inputs.dirs.last().unwrap());
+    }
+}
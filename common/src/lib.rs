@@ -0,0 +1,98 @@
+use std::fmt::Display;
+use std::hint::black_box;
+
+pub mod answers;
+pub mod bench;
+pub mod digits;
+pub mod input;
+pub mod random;
+pub mod rle;
+
+use bench::{measure, Timings};
+
+pub type Result<T> = std::result::Result<T, String>;
+
+// A single day's puzzle: parse the raw input once, then answer each part
+// from the parsed form.
+pub trait Solution {
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> impl Display;
+    fn part2(&self, input: &Self::Input) -> impl Display;
+}
+
+// The object-safe face of a Solution, so the runner can keep every day in
+// one table regardless of its input type.
+pub trait Day {
+    // Parse the input and return the answer for each of `parts`, in order.
+    fn solve(&self, input: &str, parts: &[u32]) -> Result<Vec<String>>;
+
+    // Time parsing and each part separately over `iterations` runs.
+    fn bench(&self, input: &str, iterations: usize) -> Result<Timings>;
+}
+
+impl<S: Solution> Day for S {
+    fn solve(&self, input: &str, parts: &[u32]) -> Result<Vec<String>> {
+        let parsed = self.parse(input)?;
+
+        parts.iter().map(|part| match part {
+            1 => Ok(self.part1(&parsed).to_string()),
+            2 => Ok(self.part2(&parsed).to_string()),
+            _ => Err(format!("There is no part {}", part)),
+        }).collect()
+    }
+
+    fn bench(&self, input: &str, iterations: usize) -> Result<Timings> {
+        let parsed = self.parse(input)?;
+
+        Ok(Timings {
+            parse: measure(iterations, || { black_box(self.parse(black_box(input)).ok()); }),
+            part1: measure(iterations, || { black_box(self.part1(black_box(&parsed)).to_string()); }),
+            part2: measure(iterations, || { black_box(self.part2(black_box(&parsed)).to_string()); }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+
+        fn parse(&self, input: &str) -> Result<Vec<u32>> {
+            input.split(',')
+                .map(|x| x.parse().map_err(|_| format!("{} is not a number", x)))
+                .collect()
+        }
+
+        fn part1(&self, input: &Vec<u32>) -> impl Display {
+            input.iter().sum::<u32>()
+        }
+
+        fn part2(&self, input: &Vec<u32>) -> impl Display {
+            input.len()
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(Ok(vec!["6".to_string(), "3".to_string()]), Sum.solve("1,2,3", &[1, 2]));
+        assert_eq!(Ok(vec!["3".to_string()]), Sum.solve("1,2,3", &[2]));
+        assert!(Sum.solve("1,2,3", &[3]).is_err());
+        assert!(Sum.solve("1,x", &[1]).is_err());
+    }
+
+    #[test]
+    fn test_bench() {
+        let timings = Sum.bench("1,2,3", 3).unwrap();
+
+        for (_, stats) in timings.stages().iter() {
+            assert!(stats.min <= stats.median && stats.median <= stats.max);
+        }
+        assert!(Sum.bench("1,x", 3).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+// A small seeded generator for tests that want many varied inputs, and the
+// same ones on every run. It is a plain LCG, so not for anything else.
+pub struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    pub fn new(seed: u64) -> Lcg {
+        Lcg{ state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        self.state
+    }
+
+    // A value below `limit`, taken from the high bits, which are the more
+    // random ones.
+    pub fn below(&mut self, limit: u64) -> u64 {
+        (self.next_u64() >> 33) % limit
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_repeatable() {
+        let (mut a, mut b) = (Lcg::new(7), Lcg::new(7));
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert!((0..1000).all(|_| a.below(10) < 10));
+        assert_ne!(Lcg::new(1).next_u64(), Lcg::new(2).next_u64());
+    }
+}
@@ -0,0 +1,85 @@
+use std::iter::{self, Peekable};
+
+// A stretch of equal adjacent values: the value, the index it starts at
+// and how many times it repeats.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run<T> {
+    pub value: T,
+    pub start: usize,
+    pub len: usize,
+}
+
+impl<T> Run<T> {
+    // The index just past the run.
+    pub fn end(&self) -> usize {
+        self.start + self.len
+    }
+}
+
+pub struct Runs<I: Iterator> {
+    items: Peekable<I>,
+    position: usize,
+}
+
+impl<I> Iterator for Runs<I> where I: Iterator, I::Item: PartialEq {
+    type Item = Run<I::Item>;
+
+    fn next(&mut self) -> Option<Run<I::Item>> {
+        let value = self.items.next()?;
+        let mut len = 1;
+
+        while self.items.next_if(|item| *item == value).is_some() {
+            len += 1;
+        }
+
+        let run = Run{ value, start: self.position, len };
+        self.position += len;
+        Some(run)
+    }
+}
+
+// The runs of equal adjacent items, in order. Empty input has no runs.
+pub fn runs<I: IntoIterator>(items: I) -> Runs<I::IntoIter> where I::Item: PartialEq {
+    Runs{ items: items.into_iter().peekable(), position: 0 }
+}
+
+// Each run as a value and its length, so `aaab` is `[(a, 3), (b, 1)]`.
+pub fn encode<I: IntoIterator>(items: I) -> Vec<(I::Item, usize)> where I::Item: PartialEq {
+    runs(items).map(|run| (run.value, run.len)).collect()
+}
+
+// The items `encode` was given, back from its pairs.
+pub fn decode<T: Clone, I: IntoIterator<Item = (T, usize)>>(pairs: I) -> impl Iterator<Item = T> {
+    pairs.into_iter().flat_map(|(value, len)| iter::repeat_n(value, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs() {
+        assert_eq!(
+            vec![
+                Run{ value: &1, start: 0, len: 1 },
+                Run{ value: &2, start: 1, len: 3 },
+                Run{ value: &4, start: 4, len: 2 },
+                Run{ value: &3, start: 6, len: 1 },
+            ],
+            runs(&[1, 2, 2, 2, 4, 4, 3]).collect::<Vec<Run<&u32>>>()
+        );
+        assert_eq!(4, runs("abcd".chars()).count());
+        assert_eq!(3, runs("aab".chars()).last().unwrap().end());
+        assert_eq!(None, runs(Vec::<u32>::new()).next());
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        assert_eq!(vec![('a', 3), ('b', 1), ('a', 2)], encode("aaabaa".chars()));
+        assert!(encode(Vec::<char>::new()).is_empty());
+        assert_eq!("aaabaa", decode(vec![('a', 3), ('b', 1), ('c', 0), ('a', 2)]).collect::<String>());
+
+        let words = vec!["up", "up", "left", "up"];
+        assert_eq!(words, decode(encode(words.clone())).collect::<Vec<&str>>());
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt::Display;
+
+use common::{Result, Solution};
+
+pub mod model;
+pub mod report;
+
+use model::FuelModel;
+
+// Wide enough that no mass that parses can overflow its fuel, given the
+// limits `FuelModel::new` puts on the model.
+pub type Mass = i128;
+
+pub fn fuel(mass: Mass) -> Mass {
+    (mass / 3) - 2
+}
+
+pub fn fuel_recursive(mass: Mass) -> Mass {
+    let ret = fuel(mass);
+
+    if ret > 0 {
+        ret + fuel_recursive(ret)
+    } else {
+        0
+    }
+}
+
+// Yields the fuel for the mass, then the fuel for that fuel and so on, for
+// as long as each increment is above the model's cutoff and smaller than
+// the one before. Without the second condition a model such as `round-up,
+// subtract=0` would add 1 for 1 forever.
+pub struct FuelSteps {
+    model: FuelModel,
+    current: Mass,
+    started: bool,
+}
+
+impl FuelSteps {
+    pub fn new(model: FuelModel, mass: Mass) -> FuelSteps {
+        FuelSteps{ model, current: mass, started: false }
+    }
+}
+
+impl Iterator for FuelSteps {
+    type Item = Mass;
+
+    fn next(&mut self) -> Option<Mass> {
+        let next = self.model.fuel(self.current);
+
+        if next > self.model.cutoff && (!self.started || next < self.current) {
+            self.current = next;
+            self.started = true;
+            Some(next)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn fuel_steps(mass: Mass) -> FuelSteps {
+    FuelModel::STANDARD.steps(mass)
+}
+
+// The same total as fuel_recursive without recursing.
+pub fn fuel_iterative(mass: Mass) -> Mass {
+    fuel_steps(mass).sum()
+}
+
+#[derive(Default)]
+pub struct Day1 {
+    pub model: FuelModel,
+}
+
+impl Solution for Day1 {
+    type Input = Vec<Mass>;
+
+    // Both totals are checked here, so the parts cannot overflow.
+    fn parse(&self, input: &str) -> Result<Vec<Mass>> {
+        let masses: Vec<Mass> = report::masses(input.lines()).collect::<Result<_>>()?;
+
+        report::total(masses.iter().copied(), |mass| self.model.fuel(mass))?;
+        report::total(masses.iter().copied(), |mass| self.model.total(mass))?;
+        Ok(masses)
+    }
+
+    fn part1(&self, masses: &Vec<Mass>) -> impl Display {
+        report::total(masses.iter().copied(), |mass| self.model.fuel(mass)).expect("parse checks the total")
+    }
+
+    fn part2(&self, masses: &Vec<Mass>) -> impl Display {
+        report::total(masses.iter().copied(), |mass| self.model.total(mass)).expect("parse checks the total")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::random::Lcg;
+
+    #[test]
+    fn test_fuel() {
+        assert_eq!(fuel(12), 2);
+        assert_eq!(fuel(14), 2);
+        assert_eq!(fuel(1969), 654);
+        assert_eq!(fuel(100756), 33583);
+    }
+
+    #[test]
+    fn test_fuel_steps() {
+        assert_eq!(vec![654, 216, 70, 21, 5], fuel_steps(1969).collect::<Vec<Mass>>());
+        assert_eq!(None, fuel_steps(8).next());
+        assert_eq!(None, fuel_steps(-100).next());
+    }
+
+    #[test]
+    fn test_fuel_iterative() {
+        assert_eq!(fuel_iterative(14), 2);
+        assert_eq!(fuel_iterative(1969), 966);
+        assert_eq!(fuel_iterative(100756), 50346);
+        assert_eq!(fuel_iterative(Mass::MAX), fuel_recursive(Mass::MAX));
+    }
+
+    // Compare the iterative forms with the recursive definition over every
+    // small mass, including zero and negatives, and a spread of large ones.
+    #[test]
+    fn test_fuel_iterative_matches_recursive() {
+        let mut random = Lcg::new(0x2019_0001);
+        let mut masses: Vec<Mass> = (-1000..=200_000).collect();
+
+        for _ in 0..10_000 {
+            let seed = random.next_u64();
+            masses.push(Mass::from(seed as i64));
+            masses.push(Mass::from(seed) << (seed % 64));
+        }
+        masses.extend(&[Mass::MIN, Mass::MIN + 1, Mass::MAX - 1, Mass::MAX]);
+
+        for mass in masses {
+            let steps: Vec<Mass> = fuel_steps(mass).collect();
+
+            assert_eq!(fuel_recursive(mass), fuel_iterative(mass), "mass {}", mass);
+            assert_eq!(fuel_recursive(mass), steps.iter().sum::<Mass>(), "mass {}", mass);
+            assert!(steps.windows(2).all(|pair| pair[0] > pair[1] && pair[1] > 0), "mass {}", mass);
+        }
+    }
+
+    #[test]
+    fn test_model() {
+        let standard = Day1::default();
+        let round_up = Day1{ model: FuelModel::parse("round-up").unwrap() };
+        let masses = vec![12, 14, 1969];
+
+        assert_eq!("658", standard.part1(&masses).to_string());
+        assert_eq!("970", standard.part2(&masses).to_string());
+        assert_eq!("660", round_up.part1(&masses).to_string());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(vec![12, 1969]), Day1::default().parse("12\n1969\n"));
+        assert_eq!(Err("Line 2: x is not a valid mass".to_string()), Day1::default().parse("12\nx"));
+
+        let huge = format!("{}\n", Mass::MAX).repeat(4);
+        assert_eq!(
+            Err(format!("Total fuel overflows at mass {}", Mass::MAX)),
+            Day1::default().parse(&huge)
+        );
+    }
+
+    #[test]
+    fn test_fuel_recursive() {
+        assert_eq!(fuel_recursive(14), 2);
+        assert_eq!(fuel_recursive(1969), 966);
+        assert_eq!(fuel_recursive(100756), 50346);
+    }
+}
@@ -0,0 +1,235 @@
+use std::fmt;
+
+use common::Result;
+
+use super::{FuelSteps, Mass};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Rounding {
+    // Rust's integer division, rounding toward zero as the puzzle does.
+    Truncate,
+    Floor,
+    Ceil,
+    // Round to the nearest whole unit, halves up.
+    Nearest,
+}
+
+impl Rounding {
+    fn name(&self) -> &'static str {
+        match self {
+            Rounding::Truncate => "truncate",
+            Rounding::Floor => "floor",
+            Rounding::Ceil => "ceil",
+            Rounding::Nearest => "nearest",
+        }
+    }
+
+    fn divide(&self, mass: Mass, divisor: Mass) -> Mass {
+        let floor = mass.div_euclid(divisor);
+        let remainder = mass.rem_euclid(divisor);
+
+        match self {
+            Rounding::Truncate => mass / divisor,
+            Rounding::Floor => floor,
+            Rounding::Ceil => if remainder > 0 { floor + 1 } else { floor },
+            Rounding::Nearest => if remainder >= divisor - remainder { floor + 1 } else { floor },
+        }
+    }
+}
+
+// The fuel formula: divide the mass by `divisor`, rounding as given, and
+// subtract `subtract`. Fuel for fuel keeps being added while each increment
+// is above `cutoff`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FuelModel {
+    pub divisor: Mass,
+    pub subtract: Mass,
+    pub rounding: Rounding,
+    pub cutoff: Mass,
+}
+
+pub const MAX_SUBTRACT: Mass = i64::MAX as Mass;
+
+pub const PRESETS: [(&str, FuelModel); 2] = [
+    ("standard", FuelModel::STANDARD),
+    ("round-up", FuelModel{ rounding: Rounding::Ceil, ..FuelModel::STANDARD }),
+];
+
+impl FuelModel {
+    pub const STANDARD: FuelModel = FuelModel {
+        divisor: 3,
+        subtract: 2,
+        rounding: Rounding::Truncate,
+        cutoff: 0,
+    };
+
+    // A divisor below 2 or a negative subtraction would make fuel outweigh
+    // its mass. Fuel-for-fuel finishes whatever the settings, because the
+    // steps stop as soon as an increment no longer shrinks. Subtractions
+    // are capped well inside `Mass`, so no fuel or total can overflow.
+    pub fn new(divisor: Mass, subtract: Mass, rounding: Rounding, cutoff: Mass) -> Result<FuelModel> {
+        if divisor < 2 {
+            return Err(format!("The divisor must be at least 2, not {}", divisor));
+        }
+        if !(0..=MAX_SUBTRACT).contains(&subtract) {
+            return Err(format!("The subtraction must be from 0 to {}, not {}", MAX_SUBTRACT, subtract));
+        }
+        Ok(FuelModel{ divisor, subtract, rounding, cutoff })
+    }
+
+    // A preset name, optionally followed by overrides, or just overrides of
+    // the standard model: `round-up`, `standard,cutoff=5`, `divisor=4,subtract=1`.
+    pub fn parse(s: &str) -> Result<FuelModel> {
+        let mut model = FuelModel::STANDARD;
+
+        for (index, setting) in s.split(',').map(|setting| setting.trim()).enumerate() {
+            let mut parts = setting.splitn(2, '=');
+            let key = parts.next().unwrap();
+
+            match parts.next() {
+                None if index == 0 => {
+                    model = PRESETS.iter().find(|(name, _)| *name == key).map(|(_, preset)| *preset)
+                        .ok_or(format!("Unknown fuel model {}", key))?;
+                },
+                None => return Err(format!("{} is not a valid setting; expected key=value", setting)),
+                Some(value) => {
+                    let number = || value.parse::<Mass>().map_err(|_| format!("{} is not a valid {}", value, key));
+
+                    match key {
+                        "divisor" => model.divisor = number()?,
+                        "subtract" => model.subtract = number()?,
+                        "cutoff" => model.cutoff = number()?,
+                        "rounding" => {
+                            model.rounding = match value {
+                                "truncate" => Rounding::Truncate,
+                                "floor" => Rounding::Floor,
+                                "ceil" => Rounding::Ceil,
+                                "nearest" => Rounding::Nearest,
+                                _ => return Err(format!("Unknown rounding {}", value)),
+                            }
+                        },
+                        _ => return Err(format!("Unknown fuel model setting {}", key)),
+                    }
+                },
+            }
+        }
+
+        FuelModel::new(model.divisor, model.subtract, model.rounding, model.cutoff)
+    }
+
+    pub fn fuel(&self, mass: Mass) -> Mass {
+        self.rounding.divide(mass, self.divisor) - self.subtract
+    }
+
+    pub fn steps(&self, mass: Mass) -> FuelSteps {
+        FuelSteps::new(*self, mass)
+    }
+
+    // The fuel for the mass including fuel for the fuel.
+    pub fn total(&self, mass: Mass) -> Mass {
+        self.steps(mass).sum()
+    }
+}
+
+impl Default for FuelModel {
+    fn default() -> FuelModel {
+        FuelModel::STANDARD
+    }
+}
+
+impl fmt::Display for FuelModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "divisor={},subtract={},rounding={},cutoff={}",
+            self.divisor, self.subtract, self.rounding.name(), self.cutoff
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fuel, fuel_recursive};
+
+    #[test]
+    fn test_divide() {
+        assert_eq!(-2, Rounding::Truncate.divide(-7, 3));
+        assert_eq!(-3, Rounding::Floor.divide(-7, 3));
+        assert_eq!(3, Rounding::Ceil.divide(7, 3));
+        assert_eq!(-2, Rounding::Ceil.divide(-7, 3));
+        assert_eq!(2, Rounding::Nearest.divide(7, 3));
+        assert_eq!(3, Rounding::Nearest.divide(5, 2));
+        assert_eq!(Mass::MAX / 2 + 1, Rounding::Nearest.divide(Mass::MAX, 2));
+    }
+
+    #[test]
+    fn test_standard_matches_fuel() {
+        for mass in -100..10_000 {
+            assert_eq!(fuel(mass), FuelModel::STANDARD.fuel(mass));
+            assert_eq!(fuel_recursive(mass), FuelModel::STANDARD.total(mass));
+        }
+    }
+
+    #[test]
+    fn test_cutoff() {
+        let model = FuelModel{ cutoff: 20, ..FuelModel::STANDARD };
+
+        assert_eq!(vec![654, 216, 70, 21], model.steps(1969).collect::<Vec<Mass>>());
+        assert_eq!(961, model.total(1969));
+    }
+
+    #[test]
+    fn test_steps_finish() {
+        let round_up = FuelModel::parse("round-up,subtract=0").unwrap();
+        let floor = FuelModel::parse("rounding=floor,subtract=0,cutoff=-1").unwrap();
+        let negative = FuelModel{ cutoff: -1000, ..FuelModel::STANDARD };
+
+        assert_eq!(vec![5, 2, 1], round_up.steps(14).collect::<Vec<Mass>>());
+        assert_eq!(1, round_up.total(1));
+        assert_eq!(vec![1, 0], floor.steps(5).collect::<Vec<Mass>>());
+        assert_eq!(vec![-35], negative.steps(-100).collect::<Vec<Mass>>());
+    }
+
+    #[test]
+    fn test_extremes() {
+        for rounding in &[Rounding::Truncate, Rounding::Floor, Rounding::Ceil, Rounding::Nearest] {
+            for divisor in &[2, 3, Mass::MAX] {
+                let model = FuelModel::new(*divisor, MAX_SUBTRACT, *rounding, Mass::MIN).unwrap();
+
+                for mass in &[Mass::MIN, -1, 0, Mass::MAX] {
+                    model.total(*mass);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(FuelModel::STANDARD), FuelModel::parse("standard"));
+        assert_eq!(Ok(PRESETS[1].1), FuelModel::parse("round-up"));
+        assert_eq!(
+            Ok(FuelModel{ cutoff: 5, ..PRESETS[1].1 }),
+            FuelModel::parse("round-up, cutoff=5")
+        );
+        assert_eq!(
+            Ok(FuelModel{ divisor: 4, subtract: 1, rounding: Rounding::Floor, cutoff: 0 }),
+            FuelModel::parse("divisor=4,subtract=1,rounding=floor")
+        );
+
+        assert!(FuelModel::parse("heavy").is_err());
+        assert!(FuelModel::parse("divisor=1").is_err());
+        assert!(FuelModel::parse("subtract=-1").is_err());
+        assert!(FuelModel::parse(&format!("subtract={}", Mass::MAX)).is_err());
+        assert!(FuelModel::parse("rounding=up").is_err());
+        assert!(FuelModel::parse("standard,round-up").is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let model = FuelModel{ divisor: 5, subtract: 1, rounding: Rounding::Nearest, cutoff: 2 };
+
+        assert_eq!("divisor=5,subtract=1,rounding=nearest,cutoff=2", model.to_string());
+        assert_eq!(Ok(model), FuelModel::parse(&model.to_string()));
+    }
+}
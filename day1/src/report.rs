@@ -0,0 +1,155 @@
+use common::Result;
+
+use super::model::FuelModel;
+use super::Mass;
+
+// Parse one line of a module list; `number` counts from 1 for messages.
+pub fn parse_mass(number: usize, line: &str) -> Result<Mass> {
+    let line = line.trim();
+
+    if line.is_empty() {
+        return Err(format!("Line {}: blank entry", number));
+    }
+    line.parse().map_err(|_| format!("Line {}: {} is not a valid mass", number, line))
+}
+
+// Parse masses lazily, so input can be streamed from stdin.
+pub fn masses<I, S>(lines: I) -> impl Iterator<Item = Result<Mass>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    lines.into_iter().enumerate().map(|(index, line)| parse_mass(index + 1, line.as_ref()))
+}
+
+// Sum `f` over the masses, failing rather than overflowing.
+pub fn total<I, F>(masses: I, f: F) -> Result<Mass>
+where
+    I: IntoIterator<Item = Mass>,
+    F: Fn(Mass) -> Mass,
+{
+    masses.into_iter().try_fold(0, |total: Mass, mass| {
+        total.checked_add(f(mass)).ok_or(format!("Total fuel overflows at mass {}", mass))
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ModuleFuel {
+    pub line: usize,
+    pub mass: Mass,
+    pub naive: Mass,
+    pub recursive: Mass,
+    pub depth: usize,
+}
+
+impl ModuleFuel {
+    // `depth` counts how many times fuel had to be added for the fuel itself,
+    // so a module whose fuel needs no fuel of its own has depth 0.
+    pub fn new(line: usize, mass: Mass, model: &FuelModel) -> ModuleFuel {
+        ModuleFuel {
+            line,
+            mass,
+            naive: model.fuel(mass),
+            recursive: model.total(mass),
+            depth: model.steps(mass).count().saturating_sub(1),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Report {
+    pub modules: Vec<ModuleFuel>,
+    pub naive: Mass,
+    pub recursive: Mass,
+}
+
+impl Report {
+    pub fn from_lines<I, S>(lines: I, model: &FuelModel) -> Result<Report>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut report = Report{ modules: Vec::new(), naive: 0, recursive: 0 };
+
+        for (index, mass) in masses(lines).enumerate() {
+            let module = ModuleFuel::new(index + 1, mass?, model);
+
+            report.naive = report.naive.checked_add(module.naive)
+                .ok_or(format!("Line {}: total fuel overflows", module.line))?;
+            report.recursive = report.recursive.checked_add(module.recursive)
+                .ok_or(format!("Line {}: total fuel overflows", module.line))?;
+            report.modules.push(module);
+        }
+        Ok(report)
+    }
+
+    pub fn table(&self) -> String {
+        let mut out = format!("{:>6} {:>12} {:>12} {:>12} {:>6}\n", "line", "mass", "fuel", "recursive", "depth");
+
+        for module in &self.modules {
+            out.push_str(&format!(
+                "{:>6} {:>12} {:>12} {:>12} {:>6}\n",
+                module.line, module.mass, module.naive, module.recursive, module.depth
+            ));
+        }
+        out.push_str(&format!("{:>6} {:>12} {:>12} {:>12}\n", "total", "", self.naive, self.recursive));
+        out
+    }
+
+    pub fn csv(&self) -> String {
+        let mut out = "line,mass,fuel,recursive,depth\n".to_string();
+
+        for module in &self.modules {
+            out.push_str(&format!(
+                "{},{},{},{},{}\n",
+                module.line, module.mass, module.naive, module.recursive, module.depth
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masses() {
+        assert_eq!(
+            vec![Ok(12), Err("Line 2: blank entry".to_string()), Err("Line 3: x is not a valid mass".to_string())],
+            masses(vec!["12", " ", "x"]).collect::<Vec<Result<Mass>>>()
+        );
+    }
+
+    #[test]
+    fn test_total() {
+        assert_eq!(Ok(2 + 2 + 654 + 33583), total(vec![12, 14, 1969, 100756], crate::fuel));
+        assert!(total(vec![Mass::MAX, Mass::MAX], |mass| mass).is_err());
+    }
+
+    #[test]
+    fn test_depth() {
+        let model = FuelModel::STANDARD;
+
+        assert_eq!(0, ModuleFuel::new(1, 2, &model).depth);
+        assert_eq!(0, ModuleFuel::new(1, 14, &model).depth);
+        assert_eq!(1, ModuleFuel::new(1, 33, &model).depth);
+        assert_eq!(4, ModuleFuel::new(1, 1969, &model).depth);
+    }
+
+    #[test]
+    fn test_report() {
+        let model = FuelModel::STANDARD;
+        let report = Report::from_lines("14\n1969\n".lines(), &model).unwrap();
+
+        assert_eq!(
+            vec![ModuleFuel::new(1, 14, &model), ModuleFuel{line: 2, mass: 1969, naive: 654, recursive: 966, depth: 4}],
+            report.modules
+        );
+        assert_eq!((656, 968), (report.naive, report.recursive));
+        assert_eq!("line,mass,fuel,recursive,depth\n1,14,2,2,0\n2,1969,654,966,4\n", report.csv());
+        assert_eq!(4, report.table().lines().count());
+
+        assert_eq!(Err("Line 2: blank entry".to_string()), Report::from_lines(vec!["14", ""], &model));
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt::Display;
+
+use common::{Result, Solution};
+use intcode::IntMachine;
+
+const TARGET: i32 = 19690720;
+
+// Search every noun and verb for the pair that makes the program produce
+// `target`, returning 100 * noun + verb. Pairs that make the program fail
+// are passed over.
+fn find_noun_verb(program: &IntMachine, target: i32) -> Option<i32> {
+    for noun in 0..99 {
+        for verb in 0..99 {
+            let mut machine = program.clone();
+            machine.mem.insert(1, noun);
+            machine.mem.insert(2, verb);
+
+            if machine.try_execute() == Ok(target) {
+                return Some(100 * noun + verb);
+            }
+        }
+    }
+    None
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = IntMachine;
+
+    fn parse(&self, input: &str) -> Result<IntMachine> {
+        IntMachine::try_from_string(input)
+    }
+
+    fn part1(&self, program: &IntMachine) -> impl Display {
+        program.clone().try_execute().map_or_else(|err| err, |result| result.to_string())
+    }
+
+    fn part2(&self, program: &IntMachine) -> impl Display {
+        match find_noun_verb(program, TARGET) {
+            Some(answer) => answer.to_string(),
+            None => "No noun and verb found".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let program = Day2.parse("1,1,1,4,99,5,6,0,99").unwrap();
+
+        assert_eq!("30", Day2.part1(&program).to_string());
+
+        let program = Day2.parse("1,0,0").unwrap();
+        assert_eq!("Address 3 is outside the program at ip 0", Day2.part1(&program).to_string());
+    }
+
+    #[test]
+    fn test_malformed() {
+        // Asks for input, which the search never gives.
+        let program = Day2.parse("3,0,99").unwrap();
+
+        assert_eq!("No input left for the instruction at ip 0", Day2.part1(&program).to_string());
+        assert_eq!("No noun and verb found", Day2.part2(&program).to_string());
+    }
+
+    #[test]
+    fn test_find_noun_verb() {
+        // Adds the values at the noun and verb addresses into address 0.
+        let mut program = IntMachine::from_string("1,0,0,0,99");
+        for index in 5..100 {
+            program.mem.insert(index, 0);
+        }
+        program.mem.insert(50, 7);
+
+        assert_eq!(Some(50), find_noun_verb(&program, 8));
+        assert_eq!(None, find_noun_verb(&program, 1000));
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use super::wire::Wire;
+use super::Point;
+
+// A wire coming back to a point it has already passed: it first reached
+// `point` after `entry` steps and returns to it after `reentry`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Loop {
+    pub point: Point,
+    pub entry: i32,
+    pub reentry: i32,
+}
+
+impl Loop {
+    pub fn length(&self) -> i32 {
+        self.reentry - self.entry
+    }
+}
+
+// Every point a wire visits, mapped to the steps taken to first reach it.
+// The origin counts as visited at step 0, so returning there is a loop, but
+// as with `steps_to_position` only a return gives it a step count.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WireIndex {
+    origin: Option<Point>,
+    first_visit: HashMap<Point, i32>,
+    loops: Vec<Loop>,
+}
+
+impl WireIndex {
+    pub fn new(wire: &Wire) -> WireIndex {
+        let origin = wire.segments.first().map(|seg| seg.start);
+        let mut first_visit = HashMap::new();
+        let mut loops = Vec::new();
+
+        if let Some(origin) = origin {
+            first_visit.insert(origin, 0);
+        }
+        for (pt, steps) in wire.segments.iter().flat_map(|seg| seg.points()) {
+            match first_visit.entry(pt) {
+                Entry::Occupied(entry) => loops.push(Loop{ point: pt, entry: *entry.get(), reentry: steps }),
+                Entry::Vacant(entry) => {
+                    entry.insert(steps);
+                },
+            }
+        }
+        WireIndex{ origin, first_visit, loops }
+    }
+
+    pub fn steps_to(&self, pt: &Point) -> Option<i32> {
+        if Some(*pt) == self.origin {
+            return self.loops.iter().find(|found| found.point == *pt).map(|found| found.reentry);
+        }
+        self.first_visit.get(pt).copied()
+    }
+
+    pub fn visited(&self) -> usize {
+        self.first_visit.len()
+    }
+
+    // The wire's self-crossings, in the order it makes them.
+    pub fn loops(&self) -> &[Loop] {
+        &self.loops
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{from_string, steps_to_position};
+
+    #[test]
+    fn test_steps_to() {
+        let origin = Point::new(0, 0);
+        let commands = from_string("R8,U5,L5,D7,R2,U4").unwrap();
+        let path = origin.walk(&commands);
+        let index = WireIndex::new(&Wire::new(origin, &commands));
+
+        for pt in &path {
+            assert_eq!(Some(steps_to_position(&path, pt)), index.steps_to(pt), "{}", pt);
+        }
+        assert_eq!(None, index.steps_to(&origin));
+        assert_eq!(None, index.steps_to(&Point::new(1, 1)));
+        assert_eq!(30, index.visited());
+
+        let square = WireIndex::new(&Wire::new(origin, &from_string("R2,U2,L2,D2").unwrap()));
+        assert_eq!(Some(8), square.steps_to(&origin));
+    }
+
+    #[test]
+    fn test_loops() {
+        let origin = Point::new(0, 0);
+        let index = WireIndex::new(&Wire::new(origin, &from_string("R8,U5,L5,D7,R2,U4,L6").unwrap()));
+
+        assert_eq!(
+            vec![
+                Loop{ point: Point::new(3, 0), entry: 3, reentry: 23 },
+                Loop{ point: Point::new(5, 0), entry: 5, reentry: 29 },
+                Loop{ point: Point::new(3, 2), entry: 21, reentry: 33 },
+            ],
+            index.loops()
+        );
+        assert_eq!(24, index.loops()[1].length());
+        assert!(WireIndex::new(&Wire::new(origin, &from_string("U7,R6,D4,L4").unwrap())).loops().is_empty());
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::HashSet;
+use std::convert::TryInto;
+
+use std::fmt::{self, Display};
+
+use common::{Result, Solution};
+
+pub mod index;
+pub mod metric;
+pub mod parse;
+pub mod path;
+pub mod render;
+pub mod wire;
+
+pub use parse::{from_string, ParseError};
+use wire::{fewest_steps, nearest, shared_points, Wire};
+
+// A point on the grid. Wires on a single floor stay at z = 0, which makes
+// everything here work in two dimensions as well as three.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Point {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpRight,
+    UpLeft,
+    DownRight,
+    DownLeft,
+    // Along z, between floors.
+    Forward,
+    Back,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 10] = [
+        Direction::Up, Direction::Down, Direction::Left, Direction::Right,
+        Direction::UpRight, Direction::UpLeft, Direction::DownRight, Direction::DownLeft,
+        Direction::Forward, Direction::Back,
+    ];
+
+    // Diagonals are written as compass points.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+            Direction::UpRight => "NE",
+            Direction::UpLeft => "NW",
+            Direction::DownRight => "SE",
+            Direction::DownLeft => "SW",
+            Direction::Forward => "F",
+            Direction::Back => "B",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Direction> {
+        Direction::ALL.iter().find(|dir| dir.code() == code).copied()
+    }
+
+    pub fn reverse(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::UpLeft => Direction::DownRight,
+            Direction::DownRight => Direction::UpLeft,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::Forward => Direction::Back,
+            Direction::Back => Direction::Forward,
+        }
+    }
+
+    // The (x, y, z) change of a single step.
+    pub fn delta(&self) -> (i32, i32, i32) {
+        match self {
+            Direction::Up => (0, 1, 0),
+            Direction::Down => (0, -1, 0),
+            Direction::Left => (-1, 0, 0),
+            Direction::Right => (1, 0, 0),
+            Direction::UpRight => (1, 1, 0),
+            Direction::UpLeft => (-1, 1, 0),
+            Direction::DownRight => (1, -1, 0),
+            Direction::DownLeft => (-1, -1, 0),
+            Direction::Forward => (0, 0, 1),
+            Direction::Back => (0, 0, -1),
+        }
+    }
+}
+
+#[derive(Debug,PartialEq)]
+pub struct Command {
+    pub dir: Direction,
+    pub dist: i32,
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.dir.code(), self.dist)
+    }
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Point{x, y, z: 0}
+    }
+
+    pub fn new_3d(x: i32, y: i32, z: i32) -> Point {
+        Point{x, y, z}
+    }
+
+    // The point `dist` steps away in direction `dir`.
+    pub fn moved(&self, dir: Direction, dist: i32) -> Point {
+        let (dx, dy, dz) = dir.delta();
+
+        Point::new_3d(self.x + dx * dist, self.y + dy * dist, self.z + dz * dist)
+    }
+
+    pub fn walk_command(&self, cmd: &Command) -> Vec<Point> {
+        let mut points = Vec::new();
+
+        for d in 0..cmd.dist {
+            points.push(self.moved(cmd.dir, d + 1));
+        }
+
+        points
+    }
+
+    pub fn walk(&self, path: &[Command]) -> Vec<Point> {
+        let mut points = Vec::new();
+        let mut pos = *self;
+
+        // Zero-length moves add no points and leave the position as it was.
+        for cmd in path {
+            let mut walked = pos.walk_command(cmd);
+
+            if let Some(last) = walked.last() {
+                pos = *last;
+            }
+            points.append(&mut walked);
+        }
+
+        points
+    }
+
+    pub fn manhattan_distance(&self, other: &Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    // The number of steps between the points when diagonal moves are allowed.
+    pub fn chebyshev_distance(&self, other: &Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs()).max((self.z - other.z).abs())
+    }
+}
+
+// Points on the ground floor are written in two dimensions.
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.z == 0 {
+            write!(f, "({}, {})", self.x, self.y)
+        } else {
+            write!(f, "({}, {}, {})", self.x, self.y, self.z)
+        }
+    }
+}
+
+// The wire paths in `input`, one per line, skipping blank lines.
+pub fn parse_wires(input: &str) -> Result<Vec<Vec<Command>>> {
+    input.lines().enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| from_string(line).map_err(|err| format!("Line {}: {}", index + 1, err)))
+        .collect()
+}
+
+pub fn intersections<'a>(wire1: &'a [Point], wire2: &'a [Point]) -> Vec<&'a Point> {
+    let mut wire1_set: HashSet<&Point> = HashSet::new();
+    let mut wire2_set: HashSet<&Point> = HashSet::new();
+
+    for pt in wire1 {
+        wire1_set.insert(pt);
+    }
+    for pt in wire2 {
+        wire2_set.insert(pt);
+    }
+    wire1_set.intersection(&wire2_set).copied().collect()
+}
+
+pub fn steps_to_position(path: &[Point], pt: &Point) -> i32 {
+    (path.iter().position(|r| r == pt).unwrap() + 1).try_into().unwrap()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Wire>;
+
+    // Lay each wire out as straight segments.
+    fn parse(&self, input: &str) -> Result<Vec<Wire>> {
+        let origin = Point::new(0, 0);
+        let wires: Vec<Wire> = parse_wires(input)?.iter().map(|path| Wire::new(origin, path)).collect();
+
+        if wires.len() < 2 {
+            return Err(format!("Expected at least two wires but found {}", wires.len()));
+        }
+        Ok(wires)
+    }
+
+    // Find the minimal Manhattan distance to a point any two wires share.
+    // Diagonals crossing between grid points don't count; `aoc wires` lists
+    // those separately.
+    fn part1(&self, wires: &Vec<Wire>) -> impl Display {
+        let shared = shared_points(wires, 2);
+
+        nearest(shared.iter().map(|shared| &shared.point), &Point::new(0, 0))
+            .map_or("no crossing".to_string(), |distance| distance.to_string())
+    }
+
+    // Find the shared point with the shortest total distance traversed.
+    fn part2(&self, wires: &Vec<Wire>) -> impl Display {
+        fewest_steps(&shared_points(wires, 2)).map_or("no crossing".to_string(), |steps| steps.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_manhattan_distance() {
+        let origin = Point::new(0, 0);
+
+        assert_eq!(3, origin.manhattan_distance(&Point::new(0, 3)));
+        assert_eq!(3, origin.manhattan_distance(&Point::new(1, 2)));
+        assert_eq!(12, Point::new(-3, -3).manhattan_distance(&Point::new(3, 3)));
+        assert_eq!(9, origin.manhattan_distance(&Point::new_3d(1, -2, 6)));
+    }
+
+    #[test]
+    fn test_point_3d() {
+        assert_eq!(Point::new_3d(2, 3, 0), Point::new(2, 3));
+        assert_eq!(Point::new_3d(0, 1, -4), Point::new(0, 1).moved(Direction::Back, 4));
+        assert_eq!("(2, 3)", Point::new(2, 3).to_string());
+        assert_eq!("(2, 3, -1)", Point::new_3d(2, 3, -1).to_string());
+        assert_eq!(
+            Ok(vec![Command{dir: Direction::Forward, dist: 2}, Command{dir: Direction::Forward, dist: 1}]),
+            from_string("F2,b-1")
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(
+            Ok(vec![Command{dir: Direction::Up, dist: 5}, Command{dir: Direction::Right, dist: 10}]),
+            from_string("U5,R10")
+        );
+        assert_eq!(
+            Ok(vec![Command{dir: Direction::UpLeft, dist: 2}, Command{dir: Direction::DownRight, dist: 7}]),
+            from_string("NW2,SE7")
+        );
+    }
+
+    #[test]
+    fn test_walk_command() {
+        let origin = Point::new(0, 0);
+
+        assert_eq!(
+            origin.walk_command(&Command{dir: Direction::Up, dist: 3}),
+            vec![Point::new(0, 1), Point::new(0, 2), Point::new(0, 3)]
+        );
+
+        assert_eq!(
+            origin.walk_command(&Command{dir: Direction::Down, dist: 3}),
+            vec![Point::new(0, -1), Point::new(0, -2), Point::new(0, -3)]
+        );
+
+        assert_eq!(
+            origin.walk_command(&Command{dir: Direction::Right, dist: 3}),
+            vec![Point::new(1, 0), Point::new(2, 0), Point::new(3, 0)]
+        );
+
+        assert_eq!(
+            origin.walk_command(&Command{dir: Direction::Left, dist: 3}),
+            vec![Point::new(-1, 0), Point::new(-2, 0), Point::new(-3, 0)]
+        );
+
+        assert_eq!(
+            origin.walk_command(&Command{dir: Direction::DownLeft, dist: 2}),
+            vec![Point::new(-1, -1), Point::new(-2, -2)]
+        );
+
+        assert_eq!(
+            origin.walk_command(&Command{dir: Direction::Forward, dist: 2}),
+            vec![Point::new_3d(0, 0, 1), Point::new_3d(0, 0, 2)]
+        );
+    }
+
+    #[test]
+    fn test_walk() {
+        let path = Point::new(0, 0).walk(
+            &[
+                Command{dir:Direction::Up, dist:5},
+                Command{dir:Direction::Right, dist:3},
+                Command{dir:Direction::Down, dist:2}
+            ]
+        );
+        println!("{:?}", path);
+        assert!(!path.contains(&Point::new(0, 0)));
+        assert!(path.contains(&Point::new(0, 1)));
+        assert!(path.contains(&Point::new(0, 5)));
+        assert!(path.contains(&Point::new(1, 5)));
+        assert!(path.contains(&Point::new(3, 5)));
+        assert!(path.contains(&Point::new(3, 3)));
+
+        assert_eq!(
+            vec![Point::new(0, 1), Point::new(1, 1)],
+            Point::new(0, 0).walk(&from_string("R0,U1,L0,R1").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_intersections() {
+        let origin = Point::new(0, 0);
+        let path_1 = origin.walk(&from_string("R8,U5,L5,D3").unwrap());
+        let path_2 = origin.walk(&from_string("U7,R6,D4,L4").unwrap());
+        let cross_points = intersections(&path_1, &path_2);
+
+        println!("{:?}", cross_points);
+        assert_eq!(2, cross_points.len());
+        assert!(cross_points.contains(&&Point{x:3, y:3, z:0}));
+        assert!(cross_points.contains(&&Point{x:6, y:5, z:0}));
+    }
+
+    #[test]
+    fn test_examples() {
+        let input = "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83";
+        let wires = Day3.parse(input).unwrap();
+
+        assert_eq!("159", Day3.part1(&wires).to_string());
+        assert_eq!("610", Day3.part2(&wires).to_string());
+        assert!(Day3.parse("R8,U5").is_err());
+        assert_eq!(
+            Err("Line 3: token 2 at column 4: unknown direction X".to_string()),
+            Day3.parse("R8,U5\n\nU7,X6\n").map(|wires| wires.len())
+        );
+        assert_eq!(Ok(2), Day3.parse("R8,U5\n\nU7,R6\n").map(|wires| wires.len()));
+        assert_eq!(
+            Err("Line 1: token 1 at column 1: the path is longer than 268435455 steps".to_string()),
+            Day3.parse("R2000000000,R2000000000\nU5").map(|wires| wires.len())
+        );
+
+        let apart = Day3.parse("R5\nU5").unwrap();
+        assert_eq!("no crossing", Day3.part1(&apart).to_string());
+        assert_eq!("no crossing", Day3.part2(&apart).to_string());
+    }
+
+    #[test]
+    fn test_intersections_3d() {
+        let origin = Point::new(0, 0);
+        let path_1 = origin.walk(&from_string("R2,F2,R2").unwrap());
+        let path_2 = origin.walk(&from_string("F2,R4,B2").unwrap());
+        let mut cross_points = intersections(&path_1, &path_2);
+
+        cross_points.sort_by_key(|pt| pt.x);
+        assert_eq!(vec![&Point::new_3d(2, 0, 2), &Point::new_3d(3, 0, 2), &Point::new_3d(4, 0, 2)], cross_points);
+        assert_eq!(6, steps_to_position(&path_2, &Point::new_3d(4, 0, 2)));
+        assert_eq!(6, origin.manhattan_distance(cross_points[2]));
+    }
+
+    #[test]
+    fn test_steps_to_position() {
+        let path = vec![Point::new(0, 1), Point::new(0, 2), Point::new(0, 3), Point::new(0, 4)];
+
+        assert_eq!(2, steps_to_position(&path, &Point::new(0, 2)));
+    }
+}
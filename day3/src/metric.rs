@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use common::Result;
+
+use super::wire::{SharedPoint, Wire};
+use super::{Direction, Point};
+
+// The cost of a step in each direction, indexed as `Direction::ALL`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Weights {
+    costs: [i32; 10],
+}
+
+impl Weights {
+    pub const UNIT: Weights = Weights{ costs: [1; 10] };
+
+    fn position(dir: Direction) -> usize {
+        Direction::ALL.iter().position(|other| *other == dir).unwrap()
+    }
+
+    pub fn cost(&self, dir: Direction) -> i32 {
+        self.costs[Weights::position(dir)]
+    }
+
+    pub fn set(&mut self, dir: Direction, cost: i32) {
+        self.costs[Weights::position(dir)] = cost;
+    }
+
+    // The weighted cost of the wire's walk to its first visit of `pt`.
+    pub fn steps_to(&self, wire: &Wire, pt: &Point) -> Option<i64> {
+        let mut walked = 0;
+
+        for seg in &wire.segments {
+            if let Some(steps) = seg.steps_to(pt) {
+                return Some(walked + i64::from(self.cost(seg.direction())) * i64::from(steps - seg.steps));
+            }
+            walked += i64::from(self.cost(seg.direction())) * i64::from(seg.length());
+        }
+        None
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Metric {
+    Manhattan,
+    Chebyshev,
+    Euclidean,
+    // The combined, weighted steps the passing wires take to reach a point.
+    Steps(Weights),
+}
+
+impl Metric {
+    // A metric's name, with weights for steps given as `steps:U=2,NE=3`;
+    // directions left out cost 1.
+    pub fn parse(s: &str) -> Result<Metric> {
+        let mut parts = s.splitn(2, ':');
+
+        match (parts.next().unwrap(), parts.next()) {
+            ("manhattan", None) => Ok(Metric::Manhattan),
+            ("chebyshev", None) => Ok(Metric::Chebyshev),
+            ("euclidean", None) => Ok(Metric::Euclidean),
+            ("steps", None) => Ok(Metric::Steps(Weights::UNIT)),
+            ("steps", Some(costs)) => {
+                let mut weights = Weights::UNIT;
+
+                for cost in costs.split(',').map(|cost| cost.trim()) {
+                    let mut parts = cost.splitn(2, '=');
+                    let dir = parts.next().unwrap();
+                    let value = parts.next()
+                        .and_then(|value| value.parse().ok())
+                        .filter(|value| *value > 0)
+                        .ok_or(format!("{} is not a valid cost; expected a direction and a positive number", cost))?;
+
+                    weights.set(Direction::from_code(dir).ok_or(format!("Unknown direction {}", dir))?, value);
+                }
+                Ok(Metric::Steps(weights))
+            },
+            _ => Err(format!("Unknown metric {}", s)),
+        }
+    }
+
+    // How far `shared` is from `origin`, or how costly it is to reach.
+    pub fn measure(&self, origin: &Point, shared: &SharedPoint, wires: &[Wire]) -> f64 {
+        let (dx, dy, dz) = (
+            f64::from(shared.point.x - origin.x),
+            f64::from(shared.point.y - origin.y),
+            f64::from(shared.point.z - origin.z),
+        );
+
+        match self {
+            Metric::Manhattan => f64::from(origin.manhattan_distance(&shared.point)),
+            Metric::Chebyshev => f64::from(origin.chebyshev_distance(&shared.point)),
+            Metric::Euclidean => (dx * dx + dy * dy + dz * dz).sqrt(),
+            Metric::Steps(weights) => shared.steps.iter()
+                .map(|(wire, _)| weights.steps_to(&wires[*wire], &shared.point).unwrap() as f64)
+                .sum(),
+        }
+    }
+
+    // The `k` best of the shared points, nearest or cheapest first, with
+    // ties broken by position.
+    pub fn rank<'a>(&self, origin: &Point, shared: &'a [SharedPoint], wires: &[Wire], k: usize) -> Vec<(f64, &'a SharedPoint)> {
+        let mut ranked: Vec<(f64, &SharedPoint)> = shared.iter()
+            .map(|shared| (self.measure(origin, shared, wires), shared))
+            .collect();
+
+        ranked.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        ranked.truncate(k);
+        ranked
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Metric::Manhattan => write!(f, "manhattan"),
+            Metric::Chebyshev => write!(f, "chebyshev"),
+            Metric::Euclidean => write!(f, "euclidean"),
+            Metric::Steps(weights) if *weights == Weights::UNIT => write!(f, "steps"),
+            Metric::Steps(weights) => {
+                let costs: Vec<String> = Direction::ALL.iter()
+                    .filter(|dir| weights.cost(**dir) != 1)
+                    .map(|dir| format!("{}={}", dir.code(), weights.cost(*dir)))
+                    .collect();
+
+                write!(f, "steps:{}", costs.join(","))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::from_string;
+    use crate::wire::shared_points;
+
+    fn wires() -> Vec<Wire> {
+        let origin = Point::new(0, 0);
+
+        vec![Wire::new(origin, &from_string("R8,U5,L5,D3").unwrap()), Wire::new(origin, &from_string("U7,R6,D4,L4").unwrap())]
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(Metric::Chebyshev), Metric::parse("chebyshev"));
+        assert_eq!(Ok(Metric::Steps(Weights::UNIT)), Metric::parse("steps"));
+
+        let mut weights = Weights::UNIT;
+        weights.set(Direction::Up, 2);
+        weights.set(Direction::DownLeft, 3);
+        assert_eq!(Ok(Metric::Steps(weights)), Metric::parse("steps:U=2, SW=3"));
+
+        assert!(Metric::parse("taxicab").is_err());
+        assert!(Metric::parse("steps:U=0").is_err());
+        assert!(Metric::parse("steps:N=2").is_err());
+
+        for metric in &["manhattan", "euclidean", "steps", "steps:U=2,L=3,NW=4"] {
+            assert_eq!(*metric, Metric::parse(metric).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        let wires = wires();
+        let shared = shared_points(&wires, 2);
+        let origin = Point::new(0, 0);
+
+        assert_eq!(6.0, Metric::Manhattan.measure(&origin, &shared[0], &wires));
+        assert_eq!(3.0, Metric::Chebyshev.measure(&origin, &shared[0], &wires));
+        assert_eq!(5.0, Metric::Euclidean.measure(&Point::new(-1, 0), &shared[0], &wires));
+        assert_eq!(40.0, Metric::Steps(Weights::UNIT).measure(&origin, &shared[0], &wires));
+
+        // Wire 1 reaches (6, 5) with R8, U5, L2; wire 2 with U7, R6, D2.
+        let mut weights = Weights::UNIT;
+        weights.set(Direction::Up, 10);
+        assert_eq!(Some(60), weights.steps_to(&wires[0], &Point::new(6, 5)));
+        assert_eq!(Some(78), weights.steps_to(&wires[1], &Point::new(6, 5)));
+        assert_eq!(None, weights.steps_to(&wires[1], &Point::new(8, 0)));
+
+        weights.set(Direction::UpRight, 3);
+        assert_eq!(Some(12), weights.steps_to(&Wire::new(origin, &from_string("NE5").unwrap()), &Point::new(4, 4)));
+    }
+
+    #[test]
+    fn test_rank() {
+        let wires = wires();
+        let shared = shared_points(&wires, 2);
+        let ranked = |metric: Metric, origin: Point, k: usize| -> Vec<(f64, Point)> {
+            metric.rank(&origin, &shared, &wires, k).into_iter().map(|(value, shared)| (value, shared.point)).collect()
+        };
+
+        assert_eq!(vec![(6.0, Point::new(3, 3)), (11.0, Point::new(6, 5))], ranked(Metric::Manhattan, Point::new(0, 0), 5));
+        assert_eq!(vec![(2.0, Point::new(6, 5))], ranked(Metric::Manhattan, Point::new(6, 7), 1));
+        assert_eq!(vec![(30.0, Point::new(6, 5))], ranked(Metric::Steps(Weights::UNIT), Point::new(0, 0), 1));
+        assert!(ranked(Metric::Euclidean, Point::new(0, 0), 0).is_empty());
+    }
+}
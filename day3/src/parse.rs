@@ -0,0 +1,138 @@
+use std::fmt;
+
+use super::{Command, Direction};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseErrorKind {
+    EmptyMove,
+    MissingDirection,
+    UnknownDirection(String),
+    InvalidDistance(String),
+    TooLong,
+}
+
+// The most steps a path may take in all. Coordinates, step counts and the
+// doubled and summed forms of both then always fit in an i32.
+pub const MAX_LENGTH: i32 = i32::MAX / 8;
+
+// Where a path failed to parse: which comma-separated token, and the column
+// it starts at. Both count from 1; columns count characters, not bytes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub token: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "token {} at column {}: ", self.token, self.column)?;
+        match &self.kind {
+            ParseErrorKind::EmptyMove => write!(f, "empty move"),
+            ParseErrorKind::MissingDirection => write!(f, "missing direction"),
+            ParseErrorKind::UnknownDirection(code) => write!(f, "unknown direction {}", code),
+            ParseErrorKind::InvalidDistance(dist) => write!(f, "{} is not a valid distance", dist),
+            ParseErrorKind::TooLong => write!(f, "the path is longer than {} steps", MAX_LENGTH),
+        }
+    }
+}
+
+// Parse a comma-separated path such as `R8,U5,NE3`.
+//
+// Whitespace around and inside moves is ignored, directions may be lower
+// case, and a trailing comma is allowed. A distance of zero is a move that
+// goes nowhere, and a negative distance goes the opposite way, so `U-3` is
+// read as `D3`. A blank path has no moves, and no path may be longer than
+// `MAX_LENGTH`.
+pub fn from_string(s: &str) -> Result<Vec<Command>, ParseError> {
+    let mut commands = Vec::new();
+    let mut column = 1;
+    let mut length: i32 = 0;
+    let tokens: Vec<&str> = s.split(',').collect();
+
+    for (index, token) in tokens.iter().enumerate() {
+        let trimmed = token.trim_start();
+        let error = |kind| ParseError{
+            token: index + 1,
+            column: column + token.chars().count() - trimmed.chars().count(),
+            kind,
+        };
+        let trimmed = trimmed.trim_end();
+
+        if trimmed.is_empty() {
+            let trailing_comma = index > 0 && index + 1 == tokens.len();
+
+            if !trailing_comma && tokens.len() > 1 {
+                return Err(error(ParseErrorKind::EmptyMove));
+            }
+            continue;
+        }
+
+        let code_len = trimmed.find(|c: char| !c.is_alphabetic()).unwrap_or(trimmed.len());
+        let (code, dist) = (&trimmed[..code_len], trimmed[code_len..].trim());
+
+        if code.is_empty() {
+            return Err(error(ParseErrorKind::MissingDirection));
+        }
+        let dir = Direction::from_code(&code.to_uppercase())
+            .ok_or_else(|| error(ParseErrorKind::UnknownDirection(code.to_string())))?;
+        let invalid_distance = || error(ParseErrorKind::InvalidDistance(dist.to_string()));
+        let dist: i32 = dist.parse().map_err(|_| invalid_distance())?;
+        let (dir, dist) = if dist < 0 {
+            (dir.reverse(), dist.checked_neg().ok_or_else(invalid_distance)?)
+        } else {
+            (dir, dist)
+        };
+
+        length = length.checked_add(dist).filter(|length| *length <= MAX_LENGTH)
+            .ok_or_else(|| error(ParseErrorKind::TooLong))?;
+        commands.push(Command{ dir, dist });
+        column += token.chars().count() + 1;
+    }
+    Ok(commands)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn error(token: usize, column: usize, kind: ParseErrorKind) -> Result<Vec<Command>, ParseError> {
+        Err(ParseError{ token, column, kind })
+    }
+
+    #[test]
+    fn test_lenient() {
+        assert_eq!(
+            Ok(vec![
+                Command{ dir: Direction::Up, dist: 5 },
+                Command{ dir: Direction::UpLeft, dist: 2 },
+                Command{ dir: Direction::Left, dist: 0 },
+                Command{ dir: Direction::Left, dist: 3 },
+            ]),
+            from_string(" u5 , nW 2,L0,R-3,")
+        );
+        assert_eq!(Ok(Vec::new()), from_string(""));
+        assert_eq!(Ok(Vec::new()), from_string("  "));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(error(2, 4, ParseErrorKind::EmptyMove), from_string("U5,,R2"));
+        assert_eq!(error(1, 1, ParseErrorKind::EmptyMove), from_string(",R2"));
+        assert_eq!(error(2, 5, ParseErrorKind::MissingDirection), from_string("U5, 12"));
+        assert_eq!(error(3, 7, ParseErrorKind::UnknownDirection("Ü".to_string())), from_string("U1,U2,Ü3"));
+        assert_eq!(error(2, 4, ParseErrorKind::InvalidDistance("".to_string())), from_string("U5,R"));
+        assert_eq!(error(1, 1, ParseErrorKind::InvalidDistance("5x".to_string())), from_string("U5x"));
+        assert_eq!(
+            error(1, 1, ParseErrorKind::InvalidDistance("-2147483648".to_string())),
+            from_string("U-2147483648")
+        );
+        assert_eq!(error(1, 1, ParseErrorKind::TooLong), from_string("R2000000000,R2000000000"));
+        assert_eq!(error(2, 12, ParseErrorKind::TooLong), from_string(&format!("R{},L1", MAX_LENGTH)));
+        assert_eq!(Ok(1), from_string(&format!("R{}", MAX_LENGTH)).map(|path| path.len()));
+        assert_eq!(
+            "token 2 at column 4: unknown direction Q",
+            from_string("U5,Q2").unwrap_err().to_string()
+        );
+    }
+}
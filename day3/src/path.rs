@@ -0,0 +1,106 @@
+use super::{Command, Point};
+
+// Merges consecutive moves in the same direction and cancels moves that
+// double back, so `R3,R4` becomes `R7` and `U5,D2` becomes `U3`. Moves of
+// zero go, and cancelling can leave neighbours to merge: `R1,U3,D3,R1` is `R2`.
+pub fn simplify(path: &[Command]) -> Vec<Command> {
+    let mut simplified: Vec<Command> = Vec::new();
+
+    for cmd in path.iter().filter(|cmd| cmd.dist != 0) {
+        match simplified.last_mut() {
+            Some(last) if last.dir == cmd.dir => last.dist += cmd.dist,
+            Some(last) if last.dir == cmd.dir.reverse() => {
+                last.dist -= cmd.dist;
+                if last.dist < 0 {
+                    *last = Command{ dir: cmd.dir, dist: -last.dist };
+                } else if last.dist == 0 {
+                    simplified.pop();
+                }
+            },
+            _ => simplified.push(Command{ dir: cmd.dir, dist: cmd.dist }),
+        }
+    }
+    simplified
+}
+
+// The number of steps the path takes.
+pub fn length(path: &[Command]) -> i32 {
+    path.iter().map(|cmd| cmd.dist).sum()
+}
+
+// Where the path ends, relative to where it starts.
+pub fn displacement(path: &[Command]) -> Point {
+    path.iter().fold(Point::new(0, 0), |pos, cmd| pos.moved(cmd.dir, cmd.dist))
+}
+
+// The lowest and highest corners of the box the path stays in, starting
+// from the origin.
+pub fn bounding_box(path: &[Command]) -> (Point, Point) {
+    let mut pos = Point::new(0, 0);
+    let (mut low, mut high) = (pos, pos);
+
+    for cmd in path {
+        pos = pos.moved(cmd.dir, cmd.dist);
+        low = Point::new_3d(low.x.min(pos.x), low.y.min(pos.y), low.z.min(pos.z));
+        high = Point::new_3d(high.x.max(pos.x), high.y.max(pos.y), high.z.max(pos.z));
+    }
+    (low, high)
+}
+
+// Writes a path in the `U5,R10` format `from_string` reads. Paths that
+// `from_string` produced come back exactly.
+pub fn serialize(path: &[Command]) -> String {
+    path.iter().map(|cmd| cmd.to_string()).collect::<Vec<String>>().join(",")
+}
+
+// A form of the path for comparison: two paths that simplify to the same
+// moves have the same canonical form.
+pub fn canonical(path: &[Command]) -> String {
+    serialize(&simplify(path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::from_string;
+
+    fn path(s: &str) -> Vec<Command> {
+        from_string(s).unwrap()
+    }
+
+    #[test]
+    fn test_simplify() {
+        assert_eq!(path("R7"), simplify(&path("R3,R4")));
+        assert_eq!(path("U3"), simplify(&path("U5,D2")));
+        assert_eq!(path("D3"), simplify(&path("U2,D5")));
+        assert_eq!(path("R2"), simplify(&path("R1,U3,D3,R1")));
+        assert_eq!(path("NE2,F1"), simplify(&path("NE3,R0,SW1,F1")));
+        assert!(simplify(&path("L4,R4")).is_empty());
+        assert_eq!(path("R8,U5,L5,D3"), simplify(&path("R8,U5,L5,D3")));
+    }
+
+    #[test]
+    fn test_measures() {
+        let wire = path("R8,U5,L5,D3,B2");
+
+        assert_eq!(23, length(&wire));
+        assert_eq!(Point::new_3d(3, 2, -2), displacement(&wire));
+        assert_eq!((Point::new_3d(0, 0, -2), Point::new(8, 5)), bounding_box(&wire));
+        assert_eq!((Point::new(-3, 0), Point::new(0, 0)), bounding_box(&path("L3")));
+        assert_eq!((Point::new(0, 0), Point::new(0, 0)), bounding_box(&[]));
+    }
+
+    #[test]
+    fn test_canonical() {
+        assert_eq!("U3,R7", canonical(&path("u5, d2,R3,R4,L0")));
+        assert_eq!(canonical(&path("U3,R7")), canonical(&path("U1,U2,R10,L3")));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for s in &["R75,D30,R83,U83,L12,D49,R71,U7,L72", "", "NE3,SW2,F1,B0", "U0"] {
+            assert_eq!(*s, serialize(&path(s)));
+        }
+        assert_eq!(path("D3,R2"), path(&serialize(&path("U-3, r2,"))));
+    }
+}
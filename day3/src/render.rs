@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use common::Result;
+
+use super::wire::{shared_points, Wire};
+use super::{Command, Point};
+
+// Glyphs and colors for successive wires, reused once they run out.
+const GLYPHS: [char; 6] = ['#', '*', '+', '%', '@', '&'];
+const COLORS: [&str; 6] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#9467bd", "#8c564b", "#e377c2"];
+
+// Beyond this many cells an ASCII grid is no use to anyone; draw an SVG.
+const MAX_ASCII_CELLS: i64 = 250_000;
+// The longest side of an SVG drawing, in pixels.
+const SVG_SIZE: f64 = 1000.0;
+const SVG_MARGIN: f64 = 20.0;
+
+fn lay_out(paths: &[Vec<Command>]) -> (Point, Vec<Wire>) {
+    let origin = Point::new(0, 0);
+
+    (origin, paths.iter().map(|path| Wire::new(origin, path)).collect())
+}
+
+// The lowest and highest corners of a box around the points.
+fn bounds<'a, I: IntoIterator<Item = &'a Point>>(points: I) -> (Point, Point) {
+    points.into_iter().fold((Point::new(0, 0), Point::new(0, 0)), |(low, high), pt| {
+        (Point::new(low.x.min(pt.x), low.y.min(pt.y)), Point::new(high.x.max(pt.x), high.y.max(pt.y)))
+    })
+}
+
+// Seen from above, so every floor lands on the same grid.
+fn from_above(pt: &Point) -> Point {
+    Point::new(pt.x, pt.y)
+}
+
+// Draws each wire in its own glyph, with `X` where wires cross and `o` at
+// the origin. North is up.
+pub fn ascii(paths: &[Vec<Command>]) -> Result<String> {
+    let (origin, wires) = lay_out(paths);
+    let mut cells: HashMap<Point, char> = HashMap::new();
+
+    for (number, wire) in wires.iter().enumerate() {
+        for (pt, _) in wire.segments.iter().flat_map(|seg| seg.points()) {
+            cells.insert(from_above(&pt), GLYPHS[number % GLYPHS.len()]);
+        }
+    }
+    for shared in shared_points(&wires, 2) {
+        cells.insert(from_above(&shared.point), 'X');
+    }
+    cells.insert(origin, 'o');
+
+    let (low, high) = bounds(cells.keys());
+    let (width, height) = (i64::from(high.x - low.x) + 1, i64::from(high.y - low.y) + 1);
+    if width * height > MAX_ASCII_CELLS {
+        return Err(format!("The wires cover {}x{} cells, too many to draw as ASCII", width, height));
+    }
+
+    let mut out = String::new();
+    for y in (low.y..=high.y).rev() {
+        out.extend((low.x..=high.x).map(|x| cells.get(&Point::new(x, y)).copied().unwrap_or('.')));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+// Draws the wires from above as polylines scaled to fit SVG_SIZE, marking
+// the origin, every crossing, and the nearest and fewest-step crossings.
+pub fn svg(paths: &[Vec<Command>]) -> String {
+    let (origin, wires) = lay_out(paths);
+    let corners: Vec<Point> = wires.iter().flat_map(|wire| wire.segments.iter().map(|seg| seg.end)).collect();
+    let (low, high) = bounds(corners.iter().chain(Some(&origin)));
+    let span = f64::from((high.x - low.x).max(high.y - low.y).max(1));
+    let scale = (SVG_SIZE - 2.0 * SVG_MARGIN) / span;
+    // SVG's y axis points down, so flip it to keep north up.
+    let place = |pt: &Point| (
+        SVG_MARGIN + f64::from(pt.x - low.x) * scale,
+        SVG_MARGIN + f64::from(high.y - pt.y) * scale,
+    );
+    let circle = |pt: &Point, radius: f64, fill: &str, title: String| {
+        let (x, y) = place(pt);
+
+        format!(
+            "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\"><title>{}</title></circle>\n",
+            x, y, radius, fill, title
+        )
+    };
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\">\n",
+        2.0 * SVG_MARGIN + f64::from(high.x - low.x) * scale,
+        2.0 * SVG_MARGIN + f64::from(high.y - low.y) * scale
+    );
+
+    for (number, wire) in wires.iter().enumerate() {
+        let points: Vec<String> = Some(&origin).into_iter()
+            .chain(wire.segments.iter().map(|seg| &seg.end))
+            .map(|pt| {
+                let (x, y) = place(pt);
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect();
+
+        out.push_str(&format!(
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1\"><title>wire {}</title></polyline>\n",
+            points.join(" "), COLORS[number % COLORS.len()], number + 1
+        ));
+    }
+
+    let shared = shared_points(&wires, 2);
+    for crossing in &shared {
+        out.push_str(&circle(&crossing.point, 2.0, "black", format!("{}", crossing.point)));
+    }
+    if let Some(nearest) = shared.iter().min_by_key(|shared| origin.manhattan_distance(&shared.point)) {
+        let distance = origin.manhattan_distance(&nearest.point);
+
+        out.push_str(&circle(&nearest.point, 6.0, "red", format!("nearest: {} at distance {}", nearest.point, distance)));
+    }
+    if let Some(fewest) = shared.iter().min_by_key(|shared| shared.total_steps()) {
+        out.push_str(&circle(
+            &fewest.point, 6.0, "green",
+            format!("fewest steps: {} at {} steps", fewest.point, fewest.total_steps())
+        ));
+    }
+    out.push_str(&circle(&origin, 4.0, "black", "origin".to_string()));
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::from_string;
+
+    #[test]
+    fn test_ascii() {
+        let paths = vec![from_string("R8,U5,L5,D3").unwrap(), from_string("U7,R6,D4,L4").unwrap()];
+
+        assert_eq!(
+            "\
+*******..
+*.....*..
+*..###X##
+*..#..*.#
+*.*X***.#
+*..#....#
+*.......#
+o########
+",
+            ascii(&paths).unwrap()
+        );
+        assert!(ascii(&[from_string("R1000,U1000").unwrap()]).is_err());
+    }
+
+    #[test]
+    fn test_svg() {
+        let paths = vec![from_string("R8,U5,L5,D3").unwrap(), from_string("U7,R6,D4,L4").unwrap()];
+        let drawing = svg(&paths);
+
+        assert!(drawing.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"1000\" height=\"880\">\n"));
+        assert!(drawing.contains("<polyline points=\"20.0,860.0 980.0,860.0 980.0,260.0 380.0,260.0 380.0,620.0\""));
+        assert_eq!(2, drawing.matches("<polyline").count());
+        assert_eq!(5, drawing.matches("<circle").count());
+        assert!(drawing.contains("<title>nearest: (3, 3) at distance 6</title>"));
+        assert!(drawing.contains("<title>fewest steps: (6, 5) at 30 steps</title>"));
+        assert!(drawing.ends_with("</svg>\n"));
+    }
+}
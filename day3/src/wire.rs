@@ -0,0 +1,594 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use super::{Command, Direction, Point};
+
+// A straight run of wire. Like `Point::walk`, it covers the points after
+// `start` up to and including `end`; `steps` is how far the wire had
+// travelled on reaching `start`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+    pub steps: i32,
+}
+
+impl Segment {
+    // Diagonal steps count as one step, like straight ones.
+    pub fn length(&self) -> i32 {
+        self.start.chebyshev_distance(&self.end)
+    }
+
+    pub fn direction(&self) -> Direction {
+        let delta = (
+            (self.end.x - self.start.x).signum(),
+            (self.end.y - self.start.y).signum(),
+            (self.end.z - self.start.z).signum(),
+        );
+
+        *Direction::ALL.iter().find(|dir| dir.delta() == delta).unwrap()
+    }
+
+    // Runs along x only.
+    pub fn is_horizontal(&self) -> bool {
+        self.direction() == Direction::Left || self.direction() == Direction::Right
+    }
+
+    // Runs along y only.
+    pub fn is_vertical(&self) -> bool {
+        self.direction() == Direction::Up || self.direction() == Direction::Down
+    }
+
+    // The steps taken to reach `pt` along this segment, if it covers `pt`.
+    pub fn steps_to(&self, pt: &Point) -> Option<i32> {
+        let walked = self.start.chebyshev_distance(pt);
+
+        if walked > 0 && walked <= self.length() && self.start.moved(self.direction(), walked) == *pt {
+            Some(self.steps + walked)
+        } else {
+            None
+        }
+    }
+
+    // Each point the segment covers, with the steps taken to reach it.
+    pub fn points(&self) -> impl Iterator<Item = (Point, i32)> {
+        let seg = *self;
+        let dir = seg.direction();
+
+        (1..=seg.length()).map(move |d| (seg.start.moved(dir, d), seg.steps + d))
+    }
+
+    // The (low, high) coordinates along a straight segment's axis.
+    fn span(&self) -> (i32, i32) {
+        let (a, b) = if self.is_horizontal() { (self.start.x, self.end.x) } else { (self.start.y, self.end.y) };
+
+        (a.min(b), a.max(b))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wire {
+    pub segments: Vec<Segment>,
+}
+
+impl Wire {
+    pub fn new(origin: Point, path: &[Command]) -> Wire {
+        let mut segments = Vec::new();
+        let mut pos = origin;
+        let mut steps = 0;
+
+        // Zero-length moves cover no points, so they leave no segment.
+        for cmd in path.iter().filter(|cmd| cmd.dist != 0) {
+            let end = pos.moved(cmd.dir, cmd.dist);
+
+            segments.push(Segment{ start: pos, end, steps });
+            steps += cmd.dist.abs();
+            pos = end;
+        }
+        Wire{ segments }
+    }
+
+    pub fn length(&self) -> i32 {
+        self.segments.last().map_or(0, |seg| seg.steps + seg.length())
+    }
+
+    // Steps to the first visit of `pt`, as `steps_to_position` counts them.
+    pub fn steps_to(&self, pt: &Point) -> Option<i32> {
+        self.segments.iter().find_map(|seg| seg.steps_to(pt))
+    }
+}
+
+// A point both wires pass through, with the fewest steps each takes to get there.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Crossing {
+    pub point: Point,
+    pub steps: (i32, i32),
+}
+
+impl Crossing {
+    pub fn total_steps(&self) -> i32 {
+        self.steps.0 + self.steps.1
+    }
+}
+
+// Crossings of horizontal segments with vertical ones, found by sweeping
+// across x with the horizontal segments under the sweep line kept by y.
+// Yields (point, horizontal steps, vertical steps).
+fn perpendicular(horizontal: &[&Segment], vertical: &[&Segment]) -> Vec<(Point, i32, i32)> {
+    const ADD: u8 = 0;
+    const QUERY: u8 = 1;
+    const REMOVE: u8 = 2;
+
+    let mut events = Vec::new();
+    for (index, seg) in horizontal.iter().enumerate() {
+        let (low, high) = seg.span();
+
+        events.push((low, ADD, index));
+        events.push((high, REMOVE, index));
+    }
+    for (index, seg) in vertical.iter().enumerate() {
+        events.push((seg.start.x, QUERY, index));
+    }
+    events.sort_unstable();
+
+    let mut active: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+    let mut found = Vec::new();
+
+    for (x, kind, index) in events {
+        match kind {
+            ADD => active.entry(horizontal[index].start.y).or_default().push(index),
+            REMOVE => {
+                let y = horizontal[index].start.y;
+                let indices = active.get_mut(&y).unwrap();
+
+                indices.retain(|&other| other != index);
+                if indices.is_empty() {
+                    active.remove(&y);
+                }
+            },
+            _ => {
+                let seg = vertical[index];
+                let (low, high) = seg.span();
+
+                for (&y, indices) in active.range(low..=high) {
+                    for &other in indices {
+                        // Segments on other floors share y but never meet.
+                        let pt = Point::new_3d(x, y, horizontal[other].start.z);
+
+                        if let (Some(h), Some(v)) = (horizontal[other].steps_to(&pt), seg.steps_to(&pt)) {
+                            found.push((pt, h, v));
+                        }
+                    }
+                }
+            },
+        }
+    }
+    found
+}
+
+// Points shared by segments lying along the same line.
+fn collinear(first: &[&Segment], second: &[&Segment]) -> Vec<(Point, i32, i32)> {
+    let line = |seg: &Segment| (if seg.is_horizontal() { seg.start.y } else { seg.start.x }, seg.start.z);
+    let mut by_line: HashMap<(i32, i32), Vec<&Segment>> = HashMap::new();
+    let mut found = Vec::new();
+
+    for seg in second {
+        by_line.entry(line(seg)).or_default().push(seg);
+    }
+    for seg in first {
+        for other in by_line.get(&line(seg)).into_iter().flatten() {
+            let (low, high) = seg.span();
+            let (other_low, other_high) = other.span();
+
+            for along in low.max(other_low)..=high.min(other_high) {
+                let (across, z) = line(seg);
+                let pt = if seg.is_horizontal() { Point::new_3d(along, across, z) } else { Point::new_3d(across, along, z) };
+
+                if let (Some(a), Some(b)) = (seg.steps_to(&pt), other.steps_to(&pt)) {
+                    found.push((pt, a, b));
+                }
+            }
+        }
+    }
+    found
+}
+
+// Where two diagonal segments cross between grid points. Coordinates and
+// steps are doubled to keep them whole: a crossing at (0.5, 1.5), reached
+// after 2.5 and 3.5 steps, has `doubled` (1, 3) and `steps` (5, 7).
+// Diagonals stay on one floor, so z is always whole.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HalfStepCrossing {
+    pub doubled: Point,
+    pub steps: (i32, i32),
+}
+
+impl fmt::Display for HalfStepCrossing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (x, y, z) = (self.doubled.x, self.doubled.y, self.doubled.z);
+
+        if z == 0 {
+            write!(f, "({}, {})", f64::from(x) / 2.0, f64::from(y) / 2.0)
+        } else {
+            write!(f, "({}, {}, {})", f64::from(x) / 2.0, f64::from(y) / 2.0, z / 2)
+        }
+    }
+}
+
+enum Meeting {
+    Point(Point, i32, i32),
+    HalfStep(HalfStepCrossing),
+}
+
+fn coordinates(pt: &Point) -> [i32; 3] {
+    [pt.x, pt.y, pt.z]
+}
+
+fn delta(seg: &Segment) -> [i32; 3] {
+    let (dx, dy, dz) = seg.direction().delta();
+
+    [dx, dy, dz]
+}
+
+fn cross(a: [i32; 3], b: [i32; 3]) -> [i32; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn dot(a: [i32; 3], b: [i32; 3]) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+// Where two segments meet, for pairs the sweep can't handle because one of
+// them is diagonal or runs between floors.
+fn meet(a: &Segment, b: &Segment) -> Vec<Meeting> {
+    let (da, db) = (delta(a), delta(b));
+    let (start_a, start_b) = (coordinates(&a.start), coordinates(&b.start));
+    let o = [start_b[0] - start_a[0], start_b[1] - start_a[1], start_b[2] - start_a[2]];
+    let normal = cross(da, db);
+    let along_a = |t: i32| a.start.moved(a.direction(), t);
+
+    if normal == [0, 0, 0] {
+        // Parallel segments only meet if they lie along the same line; walk
+        // the stretch of `a` that `b` overlaps.
+        if cross(o, da) != [0, 0, 0] {
+            return Vec::new();
+        }
+        let norm = dot(da, da);
+        let b_start = dot(o, da) / norm;
+        let b_end = b_start + b.length() * dot(db, da) / norm;
+
+        return (b_start.min(b_end).max(1)..=b_start.max(b_end).min(a.length()))
+            .filter_map(|t| b.steps_to(&along_a(t)).map(|steps| Meeting::Point(along_a(t), a.steps + t, steps)))
+            .collect();
+    }
+    // Lines that don't share a plane never meet.
+    if dot(o, normal) != 0 {
+        return Vec::new();
+    }
+
+    // Solve a + t·da = b + u·db for the steps t and u along each segment,
+    // in the two axes across which the segments aren't parallel. |det| is
+    // at most 2, so doubling t and u keeps them whole.
+    let k = normal.iter().position(|n| *n != 0).unwrap();
+    let (i, j) = ((k + 1) % 3, (k + 2) % 3);
+    let det = -normal[k];
+    let t2 = 2 * (db[i] * o[j] - db[j] * o[i]) / det;
+    let u2 = 2 * (da[i] * o[j] - da[j] * o[i]) / det;
+
+    if t2 <= 0 || t2 > 2 * a.length() || u2 <= 0 || u2 > 2 * b.length() {
+        Vec::new()
+    } else if t2 % 2 == 0 {
+        vec![Meeting::Point(along_a(t2 / 2), a.steps + t2 / 2, b.steps + u2 / 2)]
+    } else {
+        vec![Meeting::HalfStep(HalfStepCrossing{
+            doubled: Point::new_3d(2 * start_a[0] + da[0] * t2, 2 * start_a[1] + da[1] * t2, 2 * start_a[2] + da[2] * t2),
+            steps: (2 * a.steps + t2, 2 * b.steps + u2),
+        })]
+    }
+}
+
+// Whether the sweep can handle a segment.
+fn is_straight(seg: &Segment) -> bool {
+    seg.is_horizontal() || seg.is_vertical()
+}
+
+// Every meeting of the wires that the sweep can't find.
+fn other_meetings(wire1: &Wire, wire2: &Wire) -> Vec<Meeting> {
+    let (straight1, other1): (Vec<&Segment>, Vec<&Segment>) = wire1.segments.iter().partition(|seg| is_straight(seg));
+    let other2: Vec<&Segment> = wire2.segments.iter().filter(|seg| !is_straight(seg)).collect();
+
+    let mut meetings = Vec::new();
+    for a in &other1 {
+        for b in &wire2.segments {
+            meetings.extend(meet(a, b));
+        }
+    }
+    for a in &straight1 {
+        for b in &other2 {
+            meetings.extend(meet(a, b));
+        }
+    }
+    meetings
+}
+
+// A wire's horizontal and vertical segments.
+fn straight(wire: &Wire) -> (Vec<&Segment>, Vec<&Segment>) {
+    wire.segments.iter().filter(|seg| is_straight(seg)).partition(|seg| seg.is_horizontal())
+}
+
+// Every grid point the two wires share, ordered by x, then y, then z.
+pub fn crossings(wire1: &Wire, wire2: &Wire) -> Vec<Crossing> {
+    let ((h1, v1), (h2, v2)) = (straight(wire1), straight(wire2));
+    let mut fewest: HashMap<Point, (i32, i32)> = HashMap::new();
+
+    let found = perpendicular(&h1, &v2).into_iter()
+        .chain(perpendicular(&h2, &v1).into_iter().map(|(pt, steps2, steps1)| (pt, steps1, steps2)))
+        .chain(collinear(&h1, &h2))
+        .chain(collinear(&v1, &v2))
+        .chain(other_meetings(wire1, wire2).into_iter().filter_map(|meeting| match meeting {
+            Meeting::Point(pt, steps1, steps2) => Some((pt, steps1, steps2)),
+            Meeting::HalfStep(_) => None,
+        }));
+
+    // A wire can reach the same point more than once; keep its first visit.
+    for (pt, steps1, steps2) in found {
+        let steps = fewest.entry(pt).or_insert((steps1, steps2));
+
+        *steps = (steps.0.min(steps1), steps.1.min(steps2));
+    }
+
+    let mut crossings: Vec<Crossing> = fewest.into_iter().map(|(point, steps)| Crossing{ point, steps }).collect();
+    crossings.sort_by_key(|crossing| (crossing.point.x, crossing.point.y, crossing.point.z));
+    crossings
+}
+
+// Crossings of diagonal segments that fall between grid points, which
+// `crossings` can't report, ordered by position. They are not shared
+// points, so the day 3 answers leave them out.
+pub fn half_step_crossings(wire1: &Wire, wire2: &Wire) -> Vec<HalfStepCrossing> {
+    let mut fewest: HashMap<Point, (i32, i32)> = HashMap::new();
+
+    for meeting in other_meetings(wire1, wire2) {
+        if let Meeting::HalfStep(half) = meeting {
+            let steps = fewest.entry(half.doubled).or_insert(half.steps);
+
+            *steps = (steps.0.min(half.steps.0), steps.1.min(half.steps.1));
+        }
+    }
+
+    let mut halves: Vec<HalfStepCrossing> = fewest.into_iter()
+        .map(|(doubled, steps)| HalfStepCrossing{ doubled, steps })
+        .collect();
+    halves.sort_by_key(|half| (half.doubled.x, half.doubled.y, half.doubled.z));
+    halves
+}
+
+// A point passed by several wires, with the fewest steps each takes to reach
+// it as (wire index, steps), ordered by wire.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharedPoint {
+    pub point: Point,
+    pub steps: Vec<(usize, i32)>,
+}
+
+impl SharedPoint {
+    pub fn wires(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn total_steps(&self) -> i32 {
+        self.steps.iter().map(|(_, steps)| steps).sum()
+    }
+}
+
+// The indices of every pair among `count` wires, lower index first.
+pub fn pairs(count: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..count).flat_map(move |i| (i + 1..count).map(move |j| (i, j)))
+}
+
+// The crossings of every pair of wires, keyed by their indices.
+pub fn pairwise(wires: &[Wire]) -> Vec<((usize, usize), Vec<Crossing>)> {
+    pairs(wires.len())
+        .map(|(i, j)| ((i, j), crossings(&wires[i], &wires[j])))
+        .collect()
+}
+
+// Points passed by at least `at_least` of the wires, ordered by x, then y,
+// then z.
+// Points are found through pairwise crossings, so at least two wires are
+// always needed.
+pub fn shared_points(wires: &[Wire], at_least: usize) -> Vec<SharedPoint> {
+    let mut passing: HashMap<Point, BTreeMap<usize, i32>> = HashMap::new();
+
+    for ((i, j), found) in pairwise(wires) {
+        for crossing in found {
+            let steps = passing.entry(crossing.point).or_default();
+
+            steps.insert(i, crossing.steps.0);
+            steps.insert(j, crossing.steps.1);
+        }
+    }
+
+    let mut shared: Vec<SharedPoint> = passing.into_iter()
+        .filter(|(_, steps)| steps.len() >= at_least.max(2))
+        .map(|(point, steps)| SharedPoint{ point, steps: steps.into_iter().collect() })
+        .collect();
+    shared.sort_by_key(|shared| (shared.point.x, shared.point.y, shared.point.z));
+    shared
+}
+
+// The Manhattan distance to the shared point nearest `origin`.
+pub fn nearest<'a, I: IntoIterator<Item = &'a Point>>(points: I, origin: &Point) -> Option<i32> {
+    points.into_iter().map(|pt| origin.manhattan_distance(pt)).min()
+}
+
+// The fewest combined steps the passing wires take to reach any one point.
+pub fn fewest_steps<'a, I: IntoIterator<Item = &'a SharedPoint>>(shared: I) -> Option<i32> {
+    shared.into_iter().map(|shared| shared.total_steps()).min()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::iter;
+    use crate::{from_string, intersections, steps_to_position};
+    use common::random::Lcg;
+
+    fn wire(s: &str) -> Wire {
+        Wire::new(Point::new(0, 0), &from_string(s).unwrap())
+    }
+
+    #[test]
+    fn test_wire() {
+        let wire = wire("U5,R3,R0,D2");
+
+        assert_eq!(
+            vec![
+                Segment{ start: Point::new(0, 0), end: Point::new(0, 5), steps: 0 },
+                Segment{ start: Point::new(0, 5), end: Point::new(3, 5), steps: 5 },
+                Segment{ start: Point::new(3, 5), end: Point::new(3, 3), steps: 8 },
+            ],
+            wire.segments
+        );
+        assert_eq!(10, wire.length());
+        assert_eq!(None, wire.steps_to(&Point::new(0, 0)));
+        assert_eq!(Some(5), wire.steps_to(&Point::new(0, 5)));
+        assert_eq!(Some(10), wire.steps_to(&Point::new(3, 3)));
+        assert_eq!(None, wire.steps_to(&Point::new(1, 1)));
+        assert_eq!(
+            vec![(Point::new(2, 5), 7), (Point::new(3, 5), 8)],
+            wire.segments[1].points().skip(1).collect::<Vec<(Point, i32)>>()
+        );
+    }
+
+    #[test]
+    fn test_crossings() {
+        assert_eq!(
+            vec![
+                Crossing{ point: Point::new(3, 3), steps: (20, 20) },
+                Crossing{ point: Point::new(6, 5), steps: (15, 15) },
+            ],
+            crossings(&wire("R8,U5,L5,D3"), &wire("U7,R6,D4,L4"))
+        );
+    }
+
+    #[test]
+    fn test_crossings_overlapping() {
+        // The wires share stretches of x = 0 and x = 2, and both return to the origin.
+        let found = crossings(&wire("R2,U4,L2,D4"), &wire("U1,R4,L2,U2,L2,D3"));
+
+        assert_eq!(
+            vec![
+                Point::new(0, 0), Point::new(0, 1), Point::new(0, 2), Point::new(0, 3),
+                Point::new(2, 1), Point::new(2, 2), Point::new(2, 3),
+            ],
+            found.iter().map(|crossing| crossing.point).collect::<Vec<Point>>()
+        );
+        assert_eq!((12, 14), found[0].steps);
+        assert_eq!((11, 1), found[1].steps);
+        assert_eq!((3, 3), found[4].steps);
+    }
+
+    #[test]
+    fn test_diagonal_crossings() {
+        // NE3 meets the vertical at (2, 2), runs along the second diagonal
+        // from (1, 1) and crosses SE5 at (2.5, 2.5), between grid points.
+        let diagonal = wire("NE3");
+        let straight = wire("R2,U4");
+
+        assert_eq!(vec![Crossing{ point: Point::new(2, 2), steps: (2, 4) }], crossings(&diagonal, &straight));
+        assert_eq!(vec![Crossing{ point: Point::new(2, 2), steps: (4, 2) }], crossings(&straight, &diagonal));
+        assert!(half_step_crossings(&diagonal, &straight).is_empty());
+
+        assert_eq!(
+            vec![Point::new(1, 1), Point::new(2, 2), Point::new(3, 3)],
+            crossings(&diagonal, &wire("NE4")).iter().map(|crossing| crossing.point).collect::<Vec<Point>>()
+        );
+
+        let crossing_diagonal = wire("U5,SE5");
+        let halves = half_step_crossings(&diagonal, &crossing_diagonal);
+
+        assert!(crossings(&diagonal, &crossing_diagonal).is_empty());
+        assert_eq!(vec![HalfStepCrossing{ doubled: Point::new(5, 5), steps: (5, 15) }], halves);
+        assert_eq!("(2.5, 2.5)", halves[0].to_string());
+        assert_eq!(vec![HalfStepCrossing{ doubled: Point::new(5, 5), steps: (15, 5) }], half_step_crossings(&crossing_diagonal, &diagonal));
+    }
+
+    #[test]
+    fn test_crossings_3d() {
+        assert_eq!(
+            vec![
+                Crossing{ point: Point::new_3d(2, 0, 2), steps: (4, 4) },
+                Crossing{ point: Point::new_3d(3, 0, 2), steps: (5, 5) },
+                Crossing{ point: Point::new_3d(4, 0, 2), steps: (6, 6) },
+            ],
+            crossings(&wire("R2,F2,R2"), &wire("F2,R4"))
+        );
+        // The same runs on different floors pass over each other.
+        assert!(crossings(&wire("F1,R4"), &wire("U2,D4")).is_empty());
+        assert_eq!(
+            vec![Crossing{ point: Point::new_3d(1, 1, 2), steps: (4, 3) }],
+            crossings(&wire("R1,U1,F3"), &wire("F2,NE3"))
+        );
+    }
+
+    #[test]
+    fn test_shared_points() {
+        let wires = vec![wire("R8,U5,L5,D3"), wire("U7,R6,D4,L4"), wire("U3,R8")];
+
+        assert_eq!(
+            vec![SharedPoint{ point: Point::new(3, 3), steps: vec![(0, 20), (1, 20), (2, 6)] }],
+            shared_points(&wires, 3)
+        );
+        assert_eq!(10, shared_points(&wires, 2).len());
+        assert_eq!(shared_points(&wires, 2), shared_points(&wires, 0));
+        assert!(shared_points(&wires, 4).is_empty());
+
+        let pairs = pairwise(&wires);
+        assert_eq!(
+            vec![((0, 1), 2), ((0, 2), 2), ((1, 2), 8)],
+            pairs.iter().map(|(pair, found)| (*pair, found.len())).collect::<Vec<((usize, usize), usize)>>()
+        );
+        assert_eq!(crossings(&wires[0], &wires[1]), pairs[0].1);
+        assert_eq!(vec![(0, 1), (0, 2), (1, 2)], super::pairs(3).collect::<Vec<(usize, usize)>>());
+        assert_eq!(0, super::pairs(1).count());
+    }
+
+    #[test]
+    fn test_nearest_and_fewest_steps() {
+        let wires = vec![wire("R8,U5,L5,D3"), wire("U7,R6,D4,L4"), wire("U3,R8")];
+        let shared = shared_points(&wires, 2);
+
+        assert_eq!(Some(1), nearest(shared.iter().map(|shared| &shared.point), &Point::new(0, 0)));
+        assert_eq!(Some(2), fewest_steps(&shared));
+        assert_eq!(Some(46), fewest_steps(&shared_points(&wires, 3)));
+        assert_eq!(None, fewest_steps(iter::empty()));
+    }
+
+    #[test]
+    fn test_crossings_match_points() {
+        let mut rng = Lcg::new(0x2019_0003);
+        let mut random = |range: u64| rng.below(range);
+
+        for _ in 0..200 {
+            let mut paths = Vec::new();
+            for _ in 0..2 {
+                let path: Vec<String> = (0..1 + random(12)).map(|_| {
+                    format!("{}{}", Direction::ALL[random(Direction::ALL.len() as u64) as usize].code(), 1 + random(8))
+                }).collect();
+
+                paths.push(path.join(","));
+            }
+
+            let origin = Point::new(0, 0);
+            let (path_1, path_2) = (origin.walk(&from_string(&paths[0]).unwrap()), origin.walk(&from_string(&paths[1]).unwrap()));
+            let mut expected: Vec<Crossing> = intersections(&path_1, &path_2).into_iter().map(|pt| Crossing{
+                point: *pt,
+                steps: (steps_to_position(&path_1, pt), steps_to_position(&path_2, pt)),
+            }).collect();
+            expected.sort_by_key(|crossing| (crossing.point.x, crossing.point.y, crossing.point.z));
+
+            assert_eq!(expected, crossings(&wire(&paths[0]), &wire(&paths[1])), "{:?}", paths);
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use common::digits;
+
+use super::rules::Rule;
+use super::Format;
+
+// Digits that never fall, or that never rise.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Order {
+    Rising,
+    Falling,
+}
+
+// The order every password passing the rule must keep, if there is one.
+fn order(rule: &Rule) -> Option<Order> {
+    match rule {
+        Rule::NonDecreasing => Some(Order::Rising),
+        Rule::NonIncreasing => Some(Order::Falling),
+        Rule::And(rules) => rules.iter().find_map(order),
+        Rule::Or(rules) => {
+            let orders: Vec<Option<Order>> = rules.iter().map(order).collect();
+
+            orders.first().copied().flatten().filter(|first| orders.iter().all(|order| *order == Some(*first)))
+        },
+        _ => None,
+    }
+}
+
+// What the rest of a password needs to know about the digits before it:
+// the last digit, the length of its run so far, the digit sum, and which
+// of the rule's tests the finished runs and steps have already settled.
+// Runs and sums are capped just past the largest length or sum the rule
+// asks about, so prefixes the rule can't tell apart share a state.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+struct State {
+    last: u32,
+    run: usize,
+    sum: u32,
+    settled: u64,
+}
+
+// The tests a rule is built from, one bit of `State::settled` each.
+fn tests(rule: &Rule, found: &mut Vec<Rule>) {
+    match rule {
+        Rule::And(rules) | Rule::Or(rules) => rules.iter().for_each(|rule| tests(rule, found)),
+        Rule::Not(rule) => tests(rule, found),
+        test if !found.contains(test) => found.push(test.clone()),
+        _ => (),
+    }
+}
+
+struct Walk<'a> {
+    base: u32,
+    rule: &'a Rule,
+    order: Order,
+    tests: Vec<Rule>,
+    run_cap: usize,
+    sum_cap: u32,
+    low: Vec<u32>,
+    high: Vec<u32>,
+    digits: Vec<u32>,
+    // Counts for the digits from a position on, once they are free of the
+    // range's bounds.
+    counts: HashMap<(usize, State), usize>,
+}
+
+// The passwords still to pass over, and those found so far for a page.
+struct Page {
+    skip: usize,
+    take: usize,
+    found: Vec<u128>,
+}
+
+impl<'a> Walk<'a> {
+    fn new(format: &Format, range: &RangeInclusive<u128>, rule: &'a Rule, order: Order) -> Option<Walk<'a>> {
+        let mut found = Vec::new();
+        tests(rule, &mut found);
+        if found.len() > 64 {
+            return None;
+        }
+
+        let run_cap = found.iter().filter_map(|test| match test {
+            Rule::RunAtLeast(k) | Rule::RunExactly(k) | Rule::RunAtMost(k) => Some(k + 1),
+            _ => None,
+        }).max().unwrap_or(0);
+        let sum_cap = found.iter().filter_map(|test| match test {
+            Rule::SumAtLeast(n) | Rule::SumExactly(n) | Rule::SumAtMost(n) => Some(n.saturating_add(1)),
+            _ => None,
+        }).max().unwrap_or(0);
+
+        Some(Walk{
+            base: format.base,
+            rule,
+            order,
+            tests: found,
+            run_cap,
+            sum_cap,
+            low: format.digits_of(*range.start()),
+            high: format.digits_of(*range.end()),
+            digits: vec![0; format.digits as usize],
+            counts: HashMap::new(),
+        })
+    }
+
+    fn bit(&self, test: &Rule) -> u64 {
+        1 << self.tests.iter().position(|found| found == test).unwrap()
+    }
+
+    // The tests a run of `len` settles once it ends.
+    fn settle_run(&self, len: usize) -> u64 {
+        self.tests.iter().enumerate().fold(0, |settled, (index, test)| {
+            let settles = match test {
+                Rule::RunAtLeast(k) => len >= *k,
+                Rule::RunExactly(k) => len == *k,
+                Rule::RunAtMost(k) => len > *k,
+                _ => false,
+            };
+            settled | (settles as u64) << index
+        })
+    }
+
+    fn start(&self, digit: u32) -> State {
+        State{ last: digit, run: 1.min(self.run_cap), sum: digit.min(self.sum_cap), settled: 0 }
+    }
+
+    fn step(&self, state: State, digit: u32) -> State {
+        let sum = state.sum.saturating_add(digit).min(self.sum_cap);
+
+        if digit == state.last {
+            return State{ run: (state.run + 1).min(self.run_cap), sum, ..state };
+        }
+
+        let mut settled = state.settled | self.settle_run(state.run);
+        for (test, broken) in &[(Rule::NonDecreasing, digit < state.last), (Rule::NonIncreasing, digit > state.last)] {
+            if *broken && self.tests.contains(test) {
+                settled |= self.bit(test);
+            }
+        }
+        State{ last: digit, run: 1.min(self.run_cap), sum, settled }
+    }
+
+    // Whether the rule passes digits that ended in `state`.
+    fn passes(&self, rule: &Rule, state: &State, settled: u64) -> bool {
+        let is_settled = || settled & self.bit(rule) != 0;
+
+        match rule {
+            Rule::NonDecreasing | Rule::NonIncreasing | Rule::RunAtMost(_) => !is_settled(),
+            Rule::RunAtLeast(_) | Rule::RunExactly(_) => is_settled(),
+            Rule::SumAtLeast(n) => state.sum >= *n,
+            Rule::SumExactly(n) => state.sum == *n,
+            Rule::SumAtMost(n) => state.sum <= *n,
+            Rule::And(rules) => rules.iter().all(|rule| self.passes(rule, state, settled)),
+            Rule::Or(rules) => rules.iter().any(|rule| self.passes(rule, state, settled)),
+            Rule::Not(rule) => !self.passes(rule, state, settled),
+        }
+    }
+
+    fn finish(&self, state: &State) -> bool {
+        self.passes(self.rule, state, state.settled | self.settle_run(state.run))
+    }
+
+    // The digits that may go at `position`, staying inside the range while
+    // the digits so far match its bounds.
+    fn choices(&self, position: usize, state: &State, at_low: bool, at_high: bool) -> RangeInclusive<u32> {
+        let mut from = if at_low { self.low[position] } else { 0 };
+        let mut to = if at_high { self.high[position] } else { self.base - 1 };
+        if position > 0 {
+            match self.order {
+                Order::Rising => from = from.max(state.last),
+                Order::Falling => to = to.min(state.last),
+            }
+        }
+        from..=to
+    }
+
+    fn next(&self, position: usize, state: State, digit: u32) -> State {
+        if position == 0 { self.start(digit) } else { self.step(state, digit) }
+    }
+
+    // Count the passwords that fill in digits from `position` on.
+    fn count(&mut self, position: usize, state: State, at_low: bool, at_high: bool) -> usize {
+        if position == self.digits.len() {
+            return self.finish(&state) as usize;
+        }
+        let free = !at_low && !at_high;
+        if free {
+            if let Some(count) = self.counts.get(&(position, state)) {
+                return *count;
+            }
+        }
+
+        let mut count = 0;
+        for digit in self.choices(position, &state, at_low, at_high) {
+            count += self.count(
+                position + 1,
+                self.next(position, state, digit),
+                at_low && digit == self.low[position],
+                at_high && digit == self.high[position]
+            );
+        }
+        if free {
+            self.counts.insert((position, state), count);
+        }
+        count
+    }
+
+    // Add the passing passwords from `position` on to the page, in order.
+    // Whole branches are skipped by their count, so only the digits leading
+    // to the page are visited.
+    fn list(&mut self, position: usize, state: State, at_low: bool, at_high: bool, page: &mut Page) {
+        if position == self.digits.len() {
+            if !self.finish(&state) {
+                return;
+            }
+            if page.skip > 0 {
+                page.skip -= 1;
+            } else {
+                page.found.push(digits::from_digits(self.digits.iter().copied(), self.base).unwrap());
+            }
+            return;
+        }
+
+        for digit in self.choices(position, &state, at_low, at_high) {
+            let next = self.next(position, state, digit);
+            let (low, high) = (at_low && digit == self.low[position], at_high && digit == self.high[position]);
+            let count = self.count(position + 1, next, low, high);
+
+            if page.skip >= count {
+                page.skip -= count;
+                continue;
+            }
+            self.digits[position] = digit;
+            self.list(position + 1, next, low, high, page);
+            if page.found.len() == page.take {
+                return;
+            }
+        }
+    }
+}
+
+// The highest password the format can write that is still in the range,
+// unless the range is empty.
+fn clamp(format: &Format, range: &RangeInclusive<u128>) -> Option<RangeInclusive<u128>> {
+    let end = match format.max() {
+        Some(max) => max.min(*range.end()),
+        None => *range.end(),
+    };
+    Some(*range.start()..=end).filter(|range| range.start() <= range.end())
+}
+
+// Counts the passwords in the range that pass the rule by visiting only
+// the digits that never fall (or never rise), when the rule demands that;
+// otherwise there is nothing to skip, and None. Digits that stray from the
+// range's bounds are counted once per state, so long passwords are cheap.
+pub fn count_ordered(format: &Format, range: &RangeInclusive<u128>, rule: &Rule) -> Option<usize> {
+    let order = order(rule)?;
+    let range = match clamp(format, range) {
+        Some(range) => range,
+        None => return Some(0),
+    };
+
+    let mut walk = Walk::new(format, &range, rule, order)?;
+    let start = walk.start(0);
+    Some(walk.count(0, start, true, true))
+}
+
+// Up to `take` of the passwords `count_ordered` counts, in order, after
+// skipping the first `skip`; None when the rule keeps no order.
+pub fn list_ordered(format: &Format, range: &RangeInclusive<u128>, rule: &Rule, skip: usize, take: usize) -> Option<Vec<u128>> {
+    let order = order(rule)?;
+    let mut page = Page{ skip, take, found: Vec::new() };
+    let range = match clamp(format, range) {
+        Some(range) if take > 0 => range,
+        _ => return Some(page.found),
+    };
+
+    let mut walk = Walk::new(format, &range, rule, order)?;
+    let start = walk.start(0);
+    walk.list(0, start, true, true, &mut page);
+    Some(page.found)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::random::Lcg;
+
+    #[test]
+    fn test_order() {
+        assert_eq!(Some(Order::Rising), order(&Rule::part2()));
+        assert_eq!(Some(Order::Falling), order(&Rule::parse("run=2 & nonincreasing").unwrap()));
+        assert_eq!(Some(Order::Rising), order(&Rule::parse("nondecreasing & sum=4 | nondecreasing & run>=3").unwrap()));
+        assert_eq!(None, order(&Rule::parse("nondecreasing | nonincreasing").unwrap()));
+        assert_eq!(None, order(&Rule::parse("nondecreasing | run=2").unwrap()));
+        assert_eq!(None, order(&Rule::parse("!nondecreasing").unwrap()));
+    }
+
+    #[test]
+    fn test_count_ordered() {
+        assert_eq!(Some(1864), count_ordered(&Format::STANDARD, &(137683..=596253), &Rule::part1()));
+        assert_eq!(Some(1258), count_ordered(&Format::STANDARD, &(137683..=596253), &Rule::part2()));
+        assert_eq!(Some(0), count_ordered(&Format::STANDARD, &RangeInclusive::new(5, 4), &Rule::part1()));
+        assert_eq!(None, count_ordered(&Format::STANDARD, &(0..=99), &Rule::RunAtLeast(2)));
+    }
+
+    // Nondecreasing digits are choices of how many of each digit to use, so
+    // there are C(n + 9, 9) of them, and all 38-digit ones must repeat one.
+    #[test]
+    fn test_long_passwords() {
+        let format = Format::new(38, 10).unwrap();
+        let all = 0..=format.max().unwrap();
+
+        assert_eq!(Some(1362649145), count_ordered(&format, &all, &Rule::part1()));
+        assert_eq!(Some(1), count_ordered(&format, &all, &Rule::parse("nondecreasing & sum=0").unwrap()));
+        assert!(count_ordered(&format, &all, &Rule::part2()).unwrap() < 1362649145);
+    }
+
+    #[test]
+    fn test_list_ordered() {
+        let format = Format::new(12, 10).unwrap();
+        let range = 100000000000..=999999999999;
+
+        assert_eq!(Some(vec![111111111111, 111111111112, 111111111113]), list_ordered(&format, &range, &Rule::part1(), 0, 3));
+        assert_eq!(Some(vec![111111111119, 111111111122]), list_ordered(&format, &range, &Rule::part1(), 8, 2));
+        let count = count_ordered(&format, &range, &Rule::part1()).unwrap();
+        assert_eq!(Some(vec![999999999999]), list_ordered(&format, &range, &Rule::part1(), count - 1, 3));
+        assert_eq!(Some(vec![]), list_ordered(&format, &range, &Rule::part1(), count, 3));
+        assert_eq!(None, list_ordered(&format, &range, &Rule::RunAtLeast(2), 0, 3));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let rules: Vec<Rule> = [
+            "nondecreasing & run>=2",
+            "nondecreasing & run=2",
+            "nonincreasing & run<=2 & sum>=12",
+            "nondecreasing & !run=3 | nondecreasing & sum=9",
+            "nonincreasing",
+            "nondecreasing & (run=1 | sum>=20) & !run>=4",
+            "nondecreasing & nonincreasing & run<=6",
+            "nonincreasing & run>=0 & !run=0 & sum<=0",
+        ].iter().map(|rule| Rule::parse(rule).unwrap()).collect();
+        let formats = [Format::new(5, 10).unwrap(), Format::new(4, 16).unwrap(), Format::new(9, 3).unwrap()];
+        let mut rng = Lcg::new(4);
+        let mut random = |limit: u128| u128::from(rng.below(limit as u64));
+
+        for format in &formats {
+            let limit = format.max().unwrap() + 1;
+
+            for _ in 0..20 {
+                let (a, b) = (random(limit), random(limit));
+                let range = a.min(b)..=a.max(b);
+
+                for rule in &rules {
+                    let passing: Vec<u128> = format.passwords(&range, rule).collect();
+                    let skip = random(8) as usize;
+
+                    assert_eq!(
+                        Some(passing.len()),
+                        count_ordered(format, &range, rule),
+                        "{} in {:?} for {:?}", rule, range, format
+                    );
+                    assert_eq!(
+                        Some(passing.iter().copied().skip(skip).take(5).collect()),
+                        list_ordered(format, &range, rule, skip, 5),
+                        "page from {} of {} in {:?} for {:?}", skip, rule, range, format
+                    );
+                }
+            }
+        }
+    }
+}
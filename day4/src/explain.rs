@@ -0,0 +1,140 @@
+use std::fmt;
+
+use common::rle::{self, Run};
+
+use super::rules::Rule;
+
+// Why a password passes or fails a rule, and likewise for each rule it is
+// built from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation {
+    pub rule: String,
+    pub passed: bool,
+    pub reason: String,
+    pub parts: Vec<Explanation>,
+}
+
+impl Explanation {
+    fn write(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        writeln!(
+            f, "{:indent$}{} {}: {}", "",
+            if self.passed { "pass" } else { "fail" }, self.rule, self.reason,
+            indent = 2 * depth
+        )?;
+        for part in &self.parts {
+            part.write(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+// Positions count from 1 at the left.
+fn describe(run: &Run<&u32>) -> String {
+    format!("run of {} at positions {}-{}", run.len, run.start + 1, run.end())
+}
+
+// Describes the runs of at least two digits, none of which is `wanted`.
+fn missing(runs: &[Run<&u32>], wanted: String) -> String {
+    let repeats: Vec<String> = runs.iter().filter(|run| run.len > 1).map(describe).collect();
+
+    if repeats.is_empty() {
+        "no repeated digits".to_string()
+    } else {
+        format!("{} but no {}", repeats.join(", "), wanted)
+    }
+}
+
+// Where the digits first break the order `keeps` asks for, counting from 1.
+fn break_in_order(digits: &[u32], keeps: fn(u32, u32) -> bool) -> Option<usize> {
+    digits.windows(2).position(|pair| !keeps(pair[0], pair[1])).map(|index| index + 2)
+}
+
+pub fn explain(rule: &Rule, digits: &[u32]) -> Explanation {
+    let runs = || rle::runs(digits).collect::<Vec<Run<&u32>>>();
+    let sum: u32 = digits.iter().sum();
+    let mut parts = Vec::new();
+
+    let reason = match rule {
+        Rule::NonDecreasing => match break_in_order(digits, |a, b| a <= b) {
+            Some(position) => format!("digits decrease at position {}", position),
+            None => "digits never decrease".to_string(),
+        },
+        Rule::NonIncreasing => match break_in_order(digits, |a, b| a >= b) {
+            Some(position) => format!("digits increase at position {}", position),
+            None => "digits never increase".to_string(),
+        },
+        Rule::RunAtLeast(k) => match runs().into_iter().find(|run| run.len >= *k) {
+            Some(run) => describe(&run),
+            None => missing(&runs(), format!("run of {} or more", k)),
+        },
+        Rule::RunExactly(k) => match runs().into_iter().find(|run| run.len == *k) {
+            Some(run) => describe(&run),
+            None => missing(&runs(), format!("run of exactly {}", k)),
+        },
+        Rule::RunAtMost(k) => match runs().into_iter().find(|run| run.len > *k) {
+            Some(run) => format!("{} is longer than {}", describe(&run), k),
+            None => format!("no run is longer than {}", k),
+        },
+        Rule::SumAtLeast(n) | Rule::SumExactly(n) | Rule::SumAtMost(n) => format!(
+            "digits sum to {}, {} {}",
+            sum, if sum < *n { "less than" } else if sum > *n { "more than" } else { "exactly" }, n
+        ),
+        Rule::And(rules) | Rule::Or(rules) => {
+            parts = rules.iter().map(|rule| explain(rule, digits)).collect();
+            format!("{} of {} pass", parts.iter().filter(|part| part.passed).count(), parts.len())
+        },
+        Rule::Not(inner) => {
+            parts.push(explain(inner, digits));
+            format!("the rule it negates {}", if parts[0].passed { "passes" } else { "fails" })
+        },
+    };
+
+    Explanation{ rule: rule.to_string(), passed: rule.check(digits), reason, parts }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn reason(rule: &str, digits: &[u32]) -> String {
+        explain(&Rule::parse(rule).unwrap(), digits).reason
+    }
+
+    #[test]
+    fn test_reasons() {
+        assert_eq!("digits decrease at position 3", reason("nondecreasing", &[1, 2, 1, 3]));
+        assert_eq!("digits never decrease", reason("nondecreasing", &[1, 1, 2]));
+        assert_eq!("digits increase at position 2", reason("nonincreasing", &[1, 2]));
+        assert_eq!("run of 3 at positions 2-4", reason("run>=2", &[1, 4, 4, 4, 5]));
+        assert_eq!(
+            "run of 3 at positions 2-4 but no run of exactly 2",
+            reason("run=2", &[1, 4, 4, 4, 5, 6])
+        );
+        assert_eq!("no repeated digits", reason("run>=2", &[1, 2, 3]));
+        assert_eq!("run of 4 at positions 1-4 is longer than 3", reason("run<=3", &[7, 7, 7, 7]));
+        assert_eq!("digits sum to 6, less than 7", reason("sum>=7", &[1, 2, 3]));
+        assert_eq!("digits sum to 6, exactly 6", reason("sum=6", &[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain(&Rule::parse("nondecreasing & !run=2").unwrap(), &[1, 1, 2, 3, 3, 3]);
+
+        assert!(!explanation.passed);
+        assert_eq!(
+            "\
+fail nondecreasing & !run=2: 1 of 2 pass
+  pass nondecreasing: digits never decrease
+  fail !run=2: the rule it negates passes
+    pass run=2: run of 2 at positions 1-2
+",
+            explanation.to_string()
+        );
+    }
+}
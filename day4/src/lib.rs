@@ -0,0 +1,188 @@
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use common::digits;
+use common::{Result, Solution};
+
+pub mod count;
+pub mod explain;
+pub mod rules;
+
+use explain::Explanation;
+use rules::Rule;
+
+fn does_count(pw: u128, format: &Format, rule: &Rule) -> bool {
+    rule.check(&format.digits_of(pw))
+}
+
+// How passwords are written: a fixed number of digits in some base, with
+// leading zeros counting as digits.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Format {
+    pub digits: u32,
+    pub base: u32,
+}
+
+impl Format {
+    pub const STANDARD: Format = Format{ digits: 6, base: 10 };
+
+    pub fn new(digits: u32, base: u32) -> Result<Format> {
+        if !(2..=36).contains(&base) {
+            return Err(format!("{} is not a valid base; expected 2 to 36", base));
+        }
+        let format = Format{ digits, base };
+
+        match (digits, format.max()) {
+            (0, _) => Err("Passwords need at least one digit".to_string()),
+            (_, None) => Err(format!("{} digits in base {} do not fit in 128 bits", digits, base)),
+            _ => Ok(format),
+        }
+    }
+
+    // The largest password that can be written, if it fits.
+    pub fn max(&self) -> Option<u128> {
+        (0..self.digits).try_fold(0u128, |max, _| {
+            max.checked_mul(u128::from(self.base))?.checked_add(u128::from(self.base - 1))
+        })
+    }
+
+    // The password's digits, most significant first.
+    pub fn digits_of(&self, pw: u128) -> Vec<u32> {
+        digits::padded(pw, self.base, self.digits).rev().collect()
+    }
+
+    // The password written out in full, leading zeros and all.
+    pub fn write(&self, pw: u128) -> String {
+        digits::padded(pw, self.base, self.digits).rev()
+            .map(|digit| std::char::from_digit(digit, self.base).unwrap())
+            .collect()
+    }
+
+    // A single password, written in this format's base.
+    pub fn parse_password(&self, s: &str) -> Result<u128> {
+        u128::from_str_radix(s.trim(), self.base).ok()
+            .filter(|pw| Some(*pw) <= self.max())
+            .ok_or(format!("{} is not a valid {}-digit password in base {}", s.trim(), self.digits, self.base))
+    }
+
+    // A range like 100000-999999, written in this format's base.
+    pub fn parse_range(&self, input: &str) -> Result<RangeInclusive<u128>> {
+        let input = input.trim();
+        let bounds: Vec<&str> = input.split('-').collect();
+        match bounds.as_slice() {
+            [start, end] => match (self.parse_password(start), self.parse_password(end)) {
+                (Ok(start), Ok(end)) => Ok(start..=end),
+                _ => Err(format!("{} is not a valid range of {}-digit passwords in base {}", input, self.digits, self.base)),
+            },
+            _ => Err(format!("Expected a range like 100000-999999 but found {}", input)),
+        }
+    }
+
+    // How many passwords in the range pass the rule. Rules that need the
+    // digits in order are counted without checking every number.
+    pub fn count(&self, range: &RangeInclusive<u128>, rule: &Rule) -> usize {
+        count::count_ordered(self, range, rule)
+            .unwrap_or_else(|| self.passwords(range, rule).count())
+    }
+
+    // The passwords in the range that pass the rule, in order.
+    pub fn passwords<'a>(&'a self, range: &RangeInclusive<u128>, rule: &'a Rule) -> impl Iterator<Item = u128> + 'a {
+        range.clone().filter(move |pw| does_count(*pw, self, rule))
+    }
+
+    // One page of `passwords`: up to `take` of them after skipping `skip`.
+    // Ordered rules jump straight to the page.
+    pub fn page(&self, range: &RangeInclusive<u128>, rule: &Rule, skip: usize, take: usize) -> Vec<u128> {
+        count::list_ordered(self, range, rule, skip, take)
+            .unwrap_or_else(|| self.passwords(range, rule).skip(skip).take(take).collect())
+    }
+
+    pub fn explain(&self, pw: u128, rule: &Rule) -> Explanation {
+        explain::explain(rule, &self.digits_of(pw))
+    }
+}
+
+impl Default for Format {
+    fn default() -> Format {
+        Format::STANDARD
+    }
+}
+
+// The rules for each part can be swapped out, as can the password format.
+pub struct Day4 {
+    pub format: Format,
+    pub rules: [Rule; 2],
+}
+
+impl Default for Day4 {
+    fn default() -> Day4 {
+        Day4{ format: Format::STANDARD, rules: [Rule::part1(), Rule::part2()] }
+    }
+}
+
+impl Solution for Day4 {
+    type Input = RangeInclusive<u128>;
+
+    // The input is the puzzle's range of candidate passwords, e.g. 100000-999999.
+    fn parse(&self, input: &str) -> Result<RangeInclusive<u128>> {
+        self.format.parse_range(input)
+    }
+
+    fn part1(&self, range: &RangeInclusive<u128>) -> impl Display {
+        self.format.count(range, &self.rules[0])
+    }
+
+    fn part2(&self, range: &RangeInclusive<u128>) -> impl Display {
+        self.format.count(range, &self.rules[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(137683..=596253), Day4::default().parse("137683-596253\n"));
+        assert!(Day4::default().parse("137683").is_err());
+        assert!(Day4::default().parse("137683-x").is_err());
+        assert!(Day4::default().parse("137683-1000000").is_err());
+        assert_eq!(Ok(0xa0..=0xff), Format::new(2, 16).unwrap().parse_range("a0-FF"));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(vec![0, 0, 4, 2], Format::new(4, 10).unwrap().digits_of(42));
+        assert_eq!(vec![1, 0, 1], Format::new(3, 2).unwrap().digits_of(5));
+        assert_eq!(Some(u128::from(u64::MAX)), Format::new(16, 16).unwrap().max());
+        assert_eq!(Some(u128::MAX), Format::new(32, 16).unwrap().max());
+        assert!(Format::new(33, 16).is_err());
+        assert!(Format::new(39, 10).is_err());
+        assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 1, 2], Format::new(12, 10).unwrap().digits_of(123456789012));
+        assert!(Format::new(0, 10).is_err());
+        assert!(Format::new(6, 1).is_err());
+        assert!(Format::new(6, 37).is_err());
+
+        // 00 to 99 without a decreasing digit, and with a pair.
+        assert_eq!(10, Format::new(2, 10).unwrap().count(&(0..=99), &Rule::part1()));
+        assert_eq!(3, Format::new(2, 2).unwrap().count(&(0..=3), &Rule::NonDecreasing));
+        assert_eq!(vec![0, 11, 22], Format::new(2, 10).unwrap().passwords(&(0..=30), &Rule::part1()).collect::<Vec<u128>>());
+        assert_eq!(vec![22, 33], Format::new(2, 10).unwrap().page(&(0..=99), &Rule::part1(), 2, 2));
+        assert_eq!(vec![10, 12], Format::new(2, 10).unwrap().page(&(0..=99), &Rule::RunAtMost(1), 9, 2));
+        assert_eq!("00ff", Format::new(4, 16).unwrap().write(0xff));
+        assert_eq!(Ok(0xff), Format::new(4, 16).unwrap().parse_password("00FF"));
+    }
+
+    #[test]
+    fn test_long_passwords() {
+        // Every twelve-digit run of non-decreasing digits must repeat one.
+        let format = Format::new(12, 10).unwrap();
+        assert_eq!(293930, format.count(&(0..=format.max().unwrap()), &Rule::part1()));
+        assert!(Rule::part2().check(&format.digits_of(112345678999)));
+
+        // Six hex digits that never fall, less those that always rise.
+        let format = Format::new(6, 16).unwrap();
+        assert_eq!(54264 - 8008, format.count(&(0..=0xffffff), &Rule::part1()));
+        assert_eq!("0000ff", format.write(0xff));
+    }
+}
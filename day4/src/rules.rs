@@ -0,0 +1,283 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use common::rle;
+use common::Result;
+
+// A test of a password's digits, most significant first. Rules combine
+// with `&`, `|` and `!`, so part 2 is `nondecreasing & run=2`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rule {
+    NonDecreasing,
+    NonIncreasing,
+    // Some run of repeated digits is at least this long.
+    RunAtLeast(usize),
+    // Some run of repeated digits is exactly this long.
+    RunExactly(usize),
+    // No run of repeated digits is longer than this.
+    RunAtMost(usize),
+    SumAtLeast(u32),
+    SumExactly(u32),
+    SumAtMost(u32),
+    And(Vec<Rule>),
+    Or(Vec<Rule>),
+    Not(Box<Rule>),
+}
+
+impl Rule {
+    pub fn part1() -> Rule {
+        Rule::And(vec![Rule::NonDecreasing, Rule::RunAtLeast(2)])
+    }
+
+    pub fn part2() -> Rule {
+        Rule::And(vec![Rule::NonDecreasing, Rule::RunExactly(2)])
+    }
+
+    pub fn check(&self, digits: &[u32]) -> bool {
+        let runs = || rle::runs(digits).map(|run| run.len);
+        let sum = || digits.iter().sum::<u32>();
+
+        match self {
+            Rule::NonDecreasing => digits.windows(2).all(|pair| pair[0] <= pair[1]),
+            Rule::NonIncreasing => digits.windows(2).all(|pair| pair[0] >= pair[1]),
+            Rule::RunAtLeast(k) => runs().any(|len| len >= *k),
+            Rule::RunExactly(k) => runs().any(|len| len == *k),
+            Rule::RunAtMost(k) => runs().all(|len| len <= *k),
+            Rule::SumAtLeast(n) => sum() >= *n,
+            Rule::SumExactly(n) => sum() == *n,
+            Rule::SumAtMost(n) => sum() <= *n,
+            Rule::And(rules) => rules.iter().all(|rule| rule.check(digits)),
+            Rule::Or(rules) => rules.iter().any(|rule| rule.check(digits)),
+            Rule::Not(rule) => !rule.check(digits),
+        }
+    }
+
+    // Rules such as `nondecreasing & (run=2 | !sum<=20)`. The tests are
+    // nondecreasing, nonincreasing, and run or sum compared with >=, = or
+    // <= to a number; `&` binds tighter than `|`.
+    pub fn parse(s: &str) -> Result<Rule> {
+        let mut parser = Parser{ chars: s.chars().peekable() };
+        let rule = parser.or()?;
+
+        match parser.next_token()? {
+            None => Ok(rule),
+            Some(token) => Err(format!("Unexpected {} in rule {}", token, s)),
+        }
+    }
+
+    // Whether the rule needs brackets to appear inside `&` or `!`.
+    fn is_compound(&self) -> bool {
+        match self {
+            Rule::And(rules) | Rule::Or(rules) => rules.len() > 1,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |f: &mut fmt::Formatter, rules: &[Rule], separator: &str, bracket: fn(&Rule) -> bool| {
+            for (index, rule) in rules.iter().enumerate() {
+                if index > 0 {
+                    write!(f, " {} ", separator)?;
+                }
+                if bracket(rule) {
+                    write!(f, "({})", rule)?;
+                } else {
+                    write!(f, "{}", rule)?;
+                }
+            }
+            Ok(())
+        };
+
+        match self {
+            Rule::NonDecreasing => write!(f, "nondecreasing"),
+            Rule::NonIncreasing => write!(f, "nonincreasing"),
+            Rule::RunAtLeast(k) => write!(f, "run>={}", k),
+            Rule::RunExactly(k) => write!(f, "run={}", k),
+            Rule::RunAtMost(k) => write!(f, "run<={}", k),
+            Rule::SumAtLeast(n) => write!(f, "sum>={}", n),
+            Rule::SumExactly(n) => write!(f, "sum={}", n),
+            Rule::SumAtMost(n) => write!(f, "sum<={}", n),
+            Rule::And(rules) => join(f, rules, "&", |rule| matches!(rule, Rule::Or(_)) && rule.is_compound()),
+            Rule::Or(rules) => join(f, rules, "|", |_| false),
+            Rule::Not(rule) if rule.is_compound() => write!(f, "!({})", rule),
+            Rule::Not(rule) => write!(f, "!{}", rule),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Compare(&'static str),
+    Symbol(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Compare(op) => write!(f, "{}", op),
+            Token::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn next_token(&mut self) -> Result<Option<Token>> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let c = match self.chars.next() {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+        Ok(Some(match c {
+            '(' | ')' | '&' | '|' | '!' => Token::Symbol(c),
+            '=' => Token::Compare("="),
+            '<' | '>' => {
+                if self.chars.next_if_eq(&'=').is_none() {
+                    return Err(format!("Expected = after {}", c));
+                }
+                Token::Compare(if c == '<' { "<=" } else { ">=" })
+            },
+            c if c.is_alphanumeric() => {
+                let mut word = c.to_string();
+
+                while let Some(c) = self.chars.next_if(|c| c.is_alphanumeric()) {
+                    word.push(c);
+                }
+                Token::Word(word)
+            },
+            c => return Err(format!("Unexpected {} in rule", c)),
+        }))
+    }
+
+    fn peek_symbol(&mut self, symbol: char) -> bool {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.next_if_eq(&symbol).is_some()
+    }
+
+    fn or(&mut self) -> Result<Rule> {
+        let mut rules = vec![self.and()?];
+
+        while self.peek_symbol('|') {
+            rules.push(self.and()?);
+        }
+        Ok(if rules.len() == 1 { rules.pop().unwrap() } else { Rule::Or(rules) })
+    }
+
+    fn and(&mut self) -> Result<Rule> {
+        let mut rules = vec![self.unary()?];
+
+        while self.peek_symbol('&') {
+            rules.push(self.unary()?);
+        }
+        Ok(if rules.len() == 1 { rules.pop().unwrap() } else { Rule::And(rules) })
+    }
+
+    fn unary(&mut self) -> Result<Rule> {
+        match self.next_token()? {
+            Some(Token::Symbol('!')) => Ok(Rule::Not(Box::new(self.unary()?))),
+            Some(Token::Symbol('(')) => {
+                let rule = self.or()?;
+
+                match self.next_token()? {
+                    Some(Token::Symbol(')')) => Ok(rule),
+                    _ => Err("Expected ) to close (".to_string()),
+                }
+            },
+            Some(Token::Word(word)) => self.test(&word),
+            Some(token) => Err(format!("Expected a rule but found {}", token)),
+            None => Err("Expected a rule but the rule ended".to_string()),
+        }
+    }
+
+    fn test(&mut self, name: &str) -> Result<Rule> {
+        match name {
+            "nondecreasing" => return Ok(Rule::NonDecreasing),
+            "nonincreasing" => return Ok(Rule::NonIncreasing),
+            "run" | "sum" => (),
+            other => return Err(format!("Unknown rule {}", other)),
+        }
+
+        let op = match self.next_token()? {
+            Some(Token::Compare(op)) => op,
+            _ => return Err(format!("Expected >=, = or <= after {}", name)),
+        };
+        let value = match self.next_token()? {
+            Some(Token::Word(value)) => value,
+            _ => return Err(format!("Expected a number after {}{}", name, op)),
+        };
+        let invalid = || format!("{} is not a valid {} length", value, name);
+
+        Ok(match (name, op) {
+            ("run", ">=") => Rule::RunAtLeast(value.parse().map_err(|_| invalid())?),
+            ("run", "=") => Rule::RunExactly(value.parse().map_err(|_| invalid())?),
+            ("run", _) => Rule::RunAtMost(value.parse().map_err(|_| invalid())?),
+            (_, ">=") => Rule::SumAtLeast(value.parse().map_err(|_| invalid())?),
+            (_, "=") => Rule::SumExactly(value.parse().map_err(|_| invalid())?),
+            _ => Rule::SumAtMost(value.parse().map_err(|_| invalid())?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(Rule::part1().check(&[1, 1, 1, 1, 1, 1]));
+        assert!(!Rule::part1().check(&[2, 2, 3, 4, 5, 0]));
+        assert!(!Rule::part1().check(&[1, 2, 3, 7, 8, 9]));
+        assert!(Rule::part2().check(&[1, 1, 2, 2, 3, 3]));
+        assert!(!Rule::part2().check(&[1, 2, 3, 4, 4, 4]));
+        assert!(Rule::part2().check(&[1, 1, 1, 1, 2, 2]));
+
+        assert!(Rule::NonIncreasing.check(&[9, 9, 4, 0]));
+        assert!(Rule::RunAtMost(2).check(&[1, 1, 2, 2]));
+        assert!(!Rule::RunAtMost(2).check(&[1, 1, 1, 2]));
+        assert!(Rule::SumExactly(10).check(&[1, 2, 3, 4]));
+        assert!(Rule::Not(Box::new(Rule::SumAtLeast(11))).check(&[1, 2, 3, 4]));
+        assert!(Rule::Or(vec![Rule::SumAtMost(1), Rule::NonIncreasing]).check(&[15, 3]));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(Rule::part1()), Rule::parse("nondecreasing & run>=2"));
+        assert_eq!(Ok(Rule::part2()), Rule::parse(" nondecreasing&run = 2 "));
+        assert_eq!(
+            Ok(Rule::Or(vec![
+                Rule::And(vec![Rule::NonIncreasing, Rule::RunAtMost(3)]),
+                Rule::Not(Box::new(Rule::Or(vec![Rule::SumAtLeast(10), Rule::SumExactly(4)]))),
+            ])),
+            Rule::parse("nonincreasing & run<=3 | !(sum>=10 | sum=4)")
+        );
+
+        assert!(Rule::parse("").is_err());
+        assert!(Rule::parse("increasing").is_err());
+        assert!(Rule::parse("run>2").is_err());
+        assert!(Rule::parse("run>=x").is_err());
+        assert!(Rule::parse("(nondecreasing").is_err());
+        assert!(Rule::parse("nondecreasing)").is_err());
+        assert!(Rule::parse("nondecreasing &").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        for rule in &[
+            "nondecreasing & run>=2",
+            "nonincreasing | sum<=9 & run=2",
+            "(run=2 | run=3) & !(sum>=4 & sum<=8)",
+            "!nondecreasing",
+        ] {
+            assert_eq!(*rule, Rule::parse(rule).unwrap().to_string());
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Display;
+
+use common::{Result, Solution};
+use intcode::IntMachine;
+
+// Run the diagnostic program for the given system ID, returning the final
+// diagnostic code it outputs.
+fn diagnostic_code(program: &IntMachine, system_id: i32) -> Result<i32> {
+    let mut machine = program.clone();
+
+    machine.input.push_back(system_id);
+    machine.try_execute()?;
+    machine.output.last().copied().ok_or("The diagnostic produced no output".to_string())
+}
+
+// The code, or why the diagnostic failed.
+fn answer(code: Result<i32>) -> String {
+    code.map_or_else(|err| err, |code| code.to_string())
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = IntMachine;
+
+    fn parse(&self, input: &str) -> Result<IntMachine> {
+        IntMachine::try_from_string(input)
+    }
+
+    fn part1(&self, program: &IntMachine) -> impl Display {
+        answer(diagnostic_code(program, 1))
+    }
+
+    fn part2(&self, program: &IntMachine) -> impl Display {
+        answer(diagnostic_code(program, 5))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_code() {
+        // Outputs 1 if the input equals 8, otherwise 0.
+        let program = IntMachine::from_string("3,9,8,9,10,9,4,9,99,-1,8");
+
+        assert_eq!(Ok(1), diagnostic_code(&program, 8));
+        assert_eq!(Ok(0), diagnostic_code(&program, 5));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let silent = Day5.parse("3,0,99").unwrap();
+        let broken = Day5.parse("3,0,4,7,99").unwrap();
+
+        assert_eq!("The diagnostic produced no output", Day5.part1(&silent).to_string());
+        assert_eq!("Address 7 is outside the program at ip 2", Day5.part2(&broken).to_string());
+        assert_eq!("No input left for the instruction at ip 2", Day5.part1(&Day5.parse("3,0,3,0,99").unwrap()).to_string());
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use super::{IntMachine, Opcode};
+
+const RESET: &str = "\x1b[0m";
+const CHANGED: &str = "\x1b[1;33m";
+const INSTRUCTION_POINTER: &str = "\x1b[7m";
+
+#[derive(Debug, PartialEq)]
+pub struct MemoryChange {
+    pub address: i32,
+    pub old: Option<Opcode>,
+    pub new: Option<Opcode>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MachineDiff {
+    pub changes: Vec<MemoryChange>,
+    pub old_ip: i32,
+    pub new_ip: i32,
+    pub old_output: Vec<Opcode>,
+    pub new_output: Vec<Opcode>,
+}
+
+impl MachineDiff {
+    pub fn between(old: &IntMachine, new: &IntMachine) -> MachineDiff {
+        let addresses: BTreeSet<&i32> = old.mem.keys().chain(new.mem.keys()).collect();
+
+        MachineDiff {
+            changes: addresses.into_iter()
+                .map(|address| MemoryChange {
+                    address: *address,
+                    old: old.mem.get(address).copied(),
+                    new: new.mem.get(address).copied(),
+                })
+                .filter(|change| change.old != change.new)
+                .collect(),
+            old_ip: old.ip,
+            new_ip: new.ip,
+            old_output: old.output.clone(),
+            new_output: new.output.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.old_ip == self.new_ip && self.old_output == self.new_output
+    }
+
+    // Index of the first output value at which the two machines disagree,
+    // counting a value present on only one side as a disagreement.
+    pub fn output_divergence(&self) -> Option<usize> {
+        let common = self.old_output.iter().zip(self.new_output.iter())
+            .position(|(old, new)| old != new);
+
+        match common {
+            Some(index) => Some(index),
+            None if self.old_output.len() != self.new_output.len() =>
+                Some(self.old_output.len().min(self.new_output.len())),
+            None => None,
+        }
+    }
+}
+
+fn format_value(value: Option<Opcode>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+impl fmt::Display for MachineDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.old_ip != self.new_ip {
+            writeln!(f, "ip: {} -> {}", self.old_ip, self.new_ip)?;
+        }
+        for change in &self.changes {
+            writeln!(
+                f,
+                "[{}]: {} -> {}",
+                change.address,
+                format_value(change.old),
+                format_value(change.new)
+            )?;
+        }
+        if let Some(index) = self.output_divergence() {
+            writeln!(
+                f,
+                "output differs from index {}: {:?} -> {:?}",
+                index,
+                &self.old_output[index.min(self.old_output.len())..],
+                &self.new_output[index.min(self.new_output.len())..]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Lay out the new machine's memory as a table, `width` cells per row,
+// highlighting changed cells and the instruction pointer with ANSI colors.
+pub fn render_grid(old: &IntMachine, new: &IntMachine, width: usize) -> String {
+    let width = width.max(1);
+    let end = old.mem.keys().chain(new.mem.keys()).max().map_or(0, |max| max + 1);
+    let mut grid = String::new();
+
+    for row_start in (0..end).step_by(width) {
+        grid.push_str(&format!("{:>6} |", row_start));
+        for address in row_start..(row_start + width as i32).min(end) {
+            let value = new.mem.get(&address).copied();
+            let cell = format!("{:>8}", format_value(value));
+
+            if address == new.ip {
+                grid.push_str(&format!("{}{}{}", INSTRUCTION_POINTER, cell, RESET));
+            } else if old.mem.get(&address).copied() != value {
+                grid.push_str(&format!("{}{}{}", CHANGED, cell, RESET));
+            } else {
+                grid.push_str(&cell);
+            }
+        }
+        grid.push('\n');
+    }
+
+    grid
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_between_unchanged() {
+        let machine = IntMachine::from_string("1,0,0,0,99");
+
+        assert!(MachineDiff::between(&machine, &machine.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_between_after_execute() {
+        let before = IntMachine::from_string("1,1,1,4,99,5,6,0,99");
+        let mut after = before.clone();
+        after.execute();
+        let diff = MachineDiff::between(&before, &after);
+
+        assert_eq!(
+            vec![
+                MemoryChange{address: 0, old: Some(1), new: Some(30)},
+                MemoryChange{address: 4, old: Some(99), new: Some(2)},
+            ],
+            diff.changes
+        );
+        assert_eq!((0, 8), (diff.old_ip, diff.new_ip));
+    }
+
+    #[test]
+    fn test_output_divergence() {
+        let mut old = IntMachine::from_string("99");
+        let mut new = old.clone();
+
+        old.output = vec![1, 2, 3];
+        new.output = vec![1, 2, 3];
+        assert_eq!(None, MachineDiff::between(&old, &new).output_divergence());
+
+        new.output = vec![1, 5, 3];
+        assert_eq!(Some(1), MachineDiff::between(&old, &new).output_divergence());
+
+        new.output = vec![1, 2];
+        assert_eq!(Some(2), MachineDiff::between(&old, &new).output_divergence());
+    }
+
+    #[test]
+    fn test_display() {
+        let before = IntMachine::from_string("1,0,0,0,99");
+        let mut after = before.clone();
+        after.execute();
+
+        assert_eq!(
+            "ip: 0 -> 4\n[0]: 1 -> 2\n",
+            MachineDiff::between(&before, &after).to_string()
+        );
+    }
+
+    #[test]
+    fn test_render_grid() {
+        let before = IntMachine::from_string("1,0,0,0,99");
+        let mut after = before.clone();
+        after.execute();
+        let grid = render_grid(&before, &after, 3);
+
+        assert_eq!(2, grid.lines().count());
+        assert!(grid.contains(&format!("{}{:>8}{}", CHANGED, 2, RESET)));
+        assert!(grid.contains(&format!("{}{:>8}{}", INSTRUCTION_POINTER, 99, RESET)));
+    }
+}
@@ -0,0 +1,180 @@
+use super::{get_parameter_mode, IntMachine, Opcode, Parameter, ParameterMode};
+
+// Everything one instruction changed, enough to put the machine back the
+// way it was before the instruction ran.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step {
+    pub ip: i32,
+    pub writes: Vec<(i32, Option<Opcode>)>,
+    pub input: Option<Opcode>,
+    pub output: Option<Opcode>,
+}
+
+impl Step {
+    pub fn new(ip: i32) -> Step {
+        Step{ ip, writes: Vec::new(), input: None, output: None }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OutputOrigin {
+    pub value: Opcode,
+    pub output_ip: i32,
+    // The address the output was read from and the ip of the instruction
+    // that last wrote it, if the operand was in position mode and written
+    // during the run.
+    pub source: Option<(i32, Option<i32>)>,
+}
+
+impl IntMachine {
+    pub fn record_history(&mut self) {
+        if self.history.is_none() {
+            self.history = Some(Vec::new());
+        }
+    }
+
+    pub fn last_step(&self) -> Option<&Step> {
+        self.history.as_ref()?.last()
+    }
+
+    pub fn steps_taken(&self) -> usize {
+        self.history.as_ref().map_or(0, |history| history.len())
+    }
+
+    // Undo the most recent instruction. Returns false if there is nothing
+    // left to undo.
+    pub fn step_back(&mut self) -> bool {
+        let step = match self.history.as_mut().and_then(|history| history.pop()) {
+            Some(step) => step,
+            None => return false,
+        };
+
+        for (index, previous) in step.writes.into_iter().rev() {
+            match previous {
+                Some(value) => self.mem.insert(index, value),
+                None => self.mem.remove(&index),
+            };
+        }
+        if let Some(value) = step.input {
+            self.input.push_front(value);
+        }
+        if step.output.is_some() {
+            self.output.pop();
+        }
+        self.ip = step.ip;
+
+        true
+    }
+
+    // Undo instructions until only `steps` of them remain executed.
+    pub fn rewind_to(&mut self, steps: usize) {
+        while self.steps_taken() > steps && self.step_back() {}
+    }
+
+    // Index into the history of the last instruction that wrote `index`.
+    pub fn last_write(&self, index: i32) -> Option<usize> {
+        self.history.as_ref()?.iter()
+            .rposition(|step| step.writes.iter().any(|(address, _)| *address == index))
+    }
+
+    // Walk back to just before the last write of `index`, leaving the ip on
+    // the writing instruction. Returns false, without moving, if no recorded
+    // instruction wrote it.
+    pub fn run_back_to_write(&mut self, index: i32) -> bool {
+        match self.last_write(index) {
+            Some(position) => {
+                self.rewind_to(position);
+                true
+            },
+            None => false,
+        }
+    }
+
+    // Walk back to the instruction that produced the last output and from
+    // there to the write that produced its operand.
+    pub fn explain_last_output(&mut self) -> Option<OutputOrigin> {
+        let position = self.history.as_ref()?.iter().rposition(|step| step.output.is_some())?;
+        self.rewind_to(position);
+
+        let op = self.get_direct(self.ip);
+        let mut origin = OutputOrigin {
+            value: self.get(op, Parameter::LEFT),
+            output_ip: self.ip,
+            source: None,
+        };
+
+        if get_parameter_mode(op, Parameter::LEFT) == ParameterMode::POSITION {
+            let address = self.get_direct(self.ip + 1);
+            let writer = if self.run_back_to_write(address) { Some(self.ip) } else { None };
+
+            origin.source = Some((address, writer));
+        }
+        Some(origin)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_step_back() {
+        let mut machine = IntMachine::from_string("1,1,1,4,99,5,6,0,99");
+        let initial = machine.clone();
+        machine.record_history();
+        machine.execute();
+
+        assert_eq!(2, machine.steps_taken());
+        assert!(machine.step_back());
+        assert_eq!(4, machine.ip);
+        assert_eq!(2, machine.get_direct(4));
+        assert!(machine.step_back());
+        assert!(!machine.step_back());
+        assert_eq!(initial.mem, machine.mem);
+        assert_eq!(0, machine.ip);
+    }
+
+    #[test]
+    fn test_step_back_restores_io() {
+        let mut machine = IntMachine::from_string("3,0,4,0,99");
+        machine.record_history();
+        machine.input.push_back(42);
+        machine.execute();
+
+        assert_eq!(vec![42], machine.output);
+        machine.rewind_to(0);
+        assert!(machine.output.is_empty());
+        assert_eq!(Some(&42), machine.input.front());
+        assert_eq!(3, machine.get_direct(0));
+
+        machine.execute();
+        assert_eq!(vec![42], machine.output);
+    }
+
+    #[test]
+    fn test_run_back_to_write() {
+        let mut machine = IntMachine::from_string("1101,2,3,9,1001,9,10,9,99,0");
+        machine.record_history();
+        machine.execute();
+
+        assert_eq!(15, machine.get_direct(9));
+        assert_eq!(Some(1), machine.last_write(9));
+        assert!(machine.run_back_to_write(9));
+        assert_eq!(4, machine.ip);
+        assert_eq!(5, machine.get_direct(9));
+        assert!(!machine.run_back_to_write(100));
+    }
+
+    #[test]
+    fn test_explain_last_output() {
+        let mut machine = IntMachine::from_string("1101,2,3,9,4,9,4,9,99,0");
+        machine.record_history();
+        machine.execute();
+
+        assert_eq!(
+            Some(OutputOrigin{value: 5, output_ip: 6, source: Some((9, Some(0)))}),
+            machine.explain_last_output()
+        );
+        assert_eq!(0, machine.ip);
+    }
+}
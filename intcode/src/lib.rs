@@ -0,0 +1,487 @@
+use std::fs::File;
+use std::io::{Read, stdin};
+use std::convert::TryInto;
+use std::collections::{HashMap, VecDeque};
+
+pub mod diff;
+pub mod history;
+
+use history::Step;
+
+pub type Opcode = i32;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParameterMode {
+    POSITION,
+    IMMEDIATE,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Parameter {
+    LEFT,
+    RIGHT,
+    TARGET,
+}
+
+pub fn get_parameter_mode(op: Opcode, parameter: Parameter) -> ParameterMode {
+    let mode = match parameter {
+        Parameter::LEFT => (op % 1000) > 100,
+        Parameter::RIGHT => (op % 10000) > 1000,
+        Parameter::TARGET => false
+    };
+
+    if mode {
+        ParameterMode::IMMEDIATE
+    } else {
+        ParameterMode::POSITION
+    }
+}
+
+pub fn get_opcode_value(op: Opcode) -> Opcode {
+    op % 100
+}
+
+// Number of memory cells taken by an instruction, including the opcode.
+pub fn instruction_length(op: Opcode) -> i32 {
+    match get_opcode_value(op) {
+        1 | 2 | 7 | 8 => 4,
+        5 | 6 => 3,
+        3 | 4 => 2,
+        _ => 1,
+    }
+}
+
+#[derive(Clone, Debug)]
+#[derive(PartialEq)]
+pub struct IntMachine {
+    pub mem: HashMap<i32, Opcode>,
+    pub ip: i32,
+    pub input: VecDeque<Opcode>,
+    pub output: Vec<Opcode>,
+    history: Option<Vec<Step>>,
+}
+
+impl Default for IntMachine {
+    fn default() -> IntMachine {
+        IntMachine::new()
+    }
+}
+
+impl IntMachine {
+    pub fn new() -> IntMachine {
+        IntMachine{
+            mem: HashMap::new(),
+            ip: 0,
+            input: VecDeque::new(),
+            output: Vec::new(),
+            history: None,
+        }
+    }
+
+    pub fn from_string(s: &str) -> IntMachine {
+        IntMachine::try_from_string(s).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_from_string(s: &str) -> Result<IntMachine, String> {
+        let mut machine = IntMachine::new();
+
+        for (index, x) in s.split(',').enumerate() {
+            let op = x.trim().parse::<i32>()
+                .map_err(|_| format!("{} is not a valid opcode", x))?;
+            machine.mem.insert(index.try_into().unwrap(), op);
+        }
+        Ok(machine)
+    }
+
+    pub fn from_file(file_name: &str) -> IntMachine {
+        let mut file = File::open(file_name).unwrap();
+        let mut content = String::new();
+
+        file.read_to_string(&mut content).unwrap();
+
+        IntMachine::from_string(&content)
+    }
+
+    fn write(&mut self, index: i32, value: Opcode) {
+        let previous = self.mem.insert(index, value);
+
+        if let Some(step) = self.history.as_mut().and_then(|history| history.last_mut()) {
+            step.writes.push((index, previous));
+        }
+    }
+
+    fn read_input(&mut self) -> Result<Opcode, String> {
+        let value = match self.input.pop_front() {
+            Some(value) => value,
+            None => {
+                let mut input = String::new();
+                println!("Enter input: ");
+                stdin().read_line(&mut input).map_err(|err| format!("Unable to read input: {}", err))?;
+                input.trim().parse().map_err(|_| format!("{} is not a number", input.trim()))?
+            }
+        };
+
+        if let Some(step) = self.history.as_mut().and_then(|history| history.last_mut()) {
+            step.input = Some(value);
+        }
+        Ok(value)
+    }
+
+    fn write_output(&mut self, value: Opcode) {
+        self.output.push(value);
+
+        if let Some(step) = self.history.as_mut().and_then(|history| history.last_mut()) {
+            step.output = Some(value);
+        }
+    }
+
+    pub fn get_direct(&self, index: i32) -> i32 {
+        self.try_get_direct(index).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_get_direct(&self, index: i32) -> Result<i32, String> {
+        self.mem.get(&index).copied().ok_or(format!("Address {} is outside the program", index))
+    }
+
+    pub fn get_indirect(&self, index: i32) -> i32 {
+        self.get_direct(self.get_direct(index))
+    }
+
+    pub fn next_opcode(&self) -> Opcode {
+        get_opcode_value(self.get_direct(self.ip))
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.next_opcode() == 99
+    }
+
+    // True if the next instruction is an input with nothing queued, in which
+    // case stepping will prompt on stdin.
+    pub fn needs_input(&self) -> bool {
+        self.next_opcode() == 3 && self.input.is_empty()
+    }
+
+    fn get_parameter_index(&self, param: Parameter) -> Result<i32, String> {
+        let offset = match param {
+            Parameter::LEFT => 1,
+            Parameter::RIGHT => 2,
+            Parameter::TARGET => 3
+        };
+        self.advanced(offset)
+    }
+
+    // The address `length` past the instruction pointer.
+    fn advanced(&self, length: i32) -> Result<i32, String> {
+        self.ip.checked_add(length).ok_or(format!("Address {} + {} is outside the program", self.ip, length))
+    }
+    
+    pub fn get(&self, op: Opcode, param: Parameter) -> i32 {
+        self.try_get(op, param).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_get(&self, op: Opcode, param: Parameter) -> Result<i32, String> {
+        let mode = get_parameter_mode(op, param);
+        let index = self.get_parameter_index(param)?;
+
+        match mode {
+            ParameterMode::IMMEDIATE => self.try_get_direct(index),
+            ParameterMode::POSITION => self.try_get_direct(self.try_get_direct(index)?)
+        }
+    }
+
+    pub fn get_target(&self, _op: Opcode, param: Parameter) -> i32 {
+        self.try_get_target(param).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_get_target(&self, param: Parameter) -> Result<i32, String> {
+        self.try_get_direct(self.get_parameter_index(param)?)
+    }
+
+    pub fn step(&mut self) -> bool {
+        self.try_step().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    // As `step`, but a malformed program is an error rather than a panic.
+    // The machine is left as it was before the failing instruction.
+    pub fn try_step(&mut self) -> Result<bool, String> {
+        let op = self.try_get_direct(self.ip)?;
+
+        if get_opcode_value(op) == 99 {
+            return Ok(false);
+        }
+        if let Some(history) = self.history.as_mut() {
+            history.push(Step::new(self.ip));
+        }
+
+        let result = self.run_instruction(op);
+        if result.is_err() {
+            if let Some(history) = self.history.as_mut() {
+                history.pop();
+            }
+        }
+        result.map(|_| true).map_err(|err| format!("{} at ip {}", err, self.ip))
+    }
+
+    // Every operand is read before anything is written, so a failure
+    // changes nothing.
+    fn run_instruction(&mut self, op: Opcode) -> Result<(), String> {
+        let op_value = get_opcode_value(op);
+
+        match op_value {
+            1 | 2 | 7 | 8 => {
+                let (left, right) = (self.try_get(op, Parameter::LEFT)?, self.try_get(op, Parameter::RIGHT)?);
+                let target = self.try_get_target(Parameter::TARGET)?;
+                let value = match op_value {
+                    1 => left.checked_add(right).ok_or(format!("{} + {} overflows", left, right))?,
+                    2 => left.checked_mul(right).ok_or(format!("{} * {} overflows", left, right))?,
+                    7 => if left < right { 1 } else { 0 },
+                    _ => if left == right { 1 } else { 0 },
+                };
+
+                let next = self.advanced(4)?;
+
+                self.write(target, value);
+                self.ip = next;
+            },
+            3 => {
+                let target = self.try_get_target(Parameter::LEFT)?;
+                let next = self.advanced(2)?;
+                let value = self.read_input()?;
+
+                self.write(target, value);
+                self.ip = next;
+            },
+            4 => {
+                let value = self.try_get(op, Parameter::LEFT)?;
+                let next = self.advanced(2)?;
+
+                self.write_output(value);
+                self.ip = next;
+            },
+            5 | 6 => {
+                let (test, jump) = (self.try_get(op, Parameter::LEFT)?, self.try_get(op, Parameter::RIGHT)?);
+
+                if (test == 0) == (op_value == 6) {
+                    self.ip = jump;
+                } else {
+                    self.ip = self.advanced(3)?;
+                }
+            },
+            _ => return Err(format!("Invalid opcode {}", op)),
+        }
+        Ok(())
+    }
+
+    pub fn execute(&mut self) -> i32 {
+        while self.step() {}
+        self.get_direct(0)
+    }
+
+    // As `execute`, but a malformed program is an error rather than a
+    // panic, and so is an input instruction with nothing queued, rather
+    // than a prompt on stdin.
+    pub fn try_execute(&mut self) -> Result<i32, String> {
+        loop {
+            if get_opcode_value(self.try_get_direct(self.ip)?) == 3 && self.input.is_empty() {
+                return Err(format!("No input left for the instruction at ip {}", self.ip));
+            }
+            if !self.try_step()? {
+                return self.try_get_direct(0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_string() {
+        let map: IntMachine = IntMachine{mem:[(0, 1),(1, 9), (2, 0), (3, 99), (4, 30), (5, 40), (6, 50)].iter().cloned().collect(), ..IntMachine::new() }; 
+        assert_eq!(
+            IntMachine::from_string("1,9,0,99,30,40,50"),
+            map
+        );
+    }
+
+    #[test]
+    fn test_get_direct() {
+        assert_eq!(
+            IntMachine::from_string("1,2,3").get_direct(1),
+            2
+        );
+    }
+
+    #[test]
+    fn test_get_indirect() {
+        assert_eq!(
+            IntMachine::from_string("1,2,3").get_indirect(1),
+            3
+        );
+    }
+
+    #[test]
+    fn test_execute_addition() {
+        assert_eq!(
+            IntMachine::from_string("1,0,0,0,99").execute(),
+            2
+        );
+    }
+    
+    #[test]
+    fn test_execute_multiplication() {
+        assert_eq!(
+            IntMachine::from_string("2,0,5,0,99,3").execute(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_execute_program() {
+        assert_eq!(
+            IntMachine::from_string("1,1,1,4,99,5,6,0,99").execute(),
+            30 
+        );
+    }
+
+    #[test]
+    fn test_execute_program_indirect() {
+        assert_eq!(
+            IntMachine::from_string("1002,4,3,4,33").execute(),
+            1002
+        );
+    }
+
+    #[test]
+    #[allow(clippy::zero_prefixed_literal)]
+    fn test_get_parameter_mode() {
+        assert_eq!(
+            ParameterMode::POSITION,
+            get_parameter_mode(02, Parameter::LEFT)
+        );
+        assert_eq!(
+            ParameterMode::POSITION,
+            get_parameter_mode(02, Parameter::RIGHT)
+        );
+        assert_eq!(
+            ParameterMode::POSITION,
+            get_parameter_mode(02, Parameter::TARGET)
+        );
+        assert_eq!(
+            ParameterMode::IMMEDIATE,
+            get_parameter_mode(102, Parameter::LEFT)
+        );
+        assert_eq!(
+            ParameterMode::IMMEDIATE,
+            get_parameter_mode(1002, Parameter::RIGHT)
+        );
+        assert_eq!(
+            ParameterMode::POSITION,
+            get_parameter_mode(10002, Parameter::TARGET)
+        );
+        assert_eq!(
+            ParameterMode::POSITION,
+            get_parameter_mode(10002, Parameter::LEFT)
+        );
+        assert_eq!(
+            ParameterMode::POSITION,
+            get_parameter_mode(10002, Parameter::RIGHT)
+        );
+    }
+
+    #[test]
+    fn test_try_from_string() {
+        assert_eq!(Ok(IntMachine::from_string("1,2,3")), IntMachine::try_from_string("1, 2,3\n"));
+        assert_eq!(
+            Err("x is not a valid opcode".to_string()),
+            IntMachine::try_from_string("1,x,3")
+        );
+    }
+
+    #[test]
+    fn test_instruction_length() {
+        assert_eq!(4, instruction_length(1101));
+        assert_eq!(2, instruction_length(104));
+        assert_eq!(3, instruction_length(1005));
+        assert_eq!(1, instruction_length(99));
+    }
+
+    #[test]
+    fn test_needs_input() {
+        let mut machine = IntMachine::from_string("3,0,99");
+
+        assert!(machine.needs_input());
+        machine.input.push_back(7);
+        assert!(!machine.needs_input());
+        assert!(machine.step());
+        assert!(machine.is_halted());
+    }
+
+    #[test]
+    fn test_try_step() {
+        let mut machine = IntMachine::from_string("1,0,7,0,99");
+        machine.record_history();
+        let before = machine.clone();
+
+        assert_eq!(Err("Address 7 is outside the program at ip 0".to_string()), machine.try_step());
+        assert_eq!(before, machine);
+        assert_eq!(
+            Err("Invalid opcode 42 at ip 0".to_string()),
+            IntMachine::from_string("42,0,0,0").try_step()
+        );
+        assert_eq!(Ok(true), IntMachine::from_string("1,0,0,0,99").try_step());
+
+        let mut machine = IntMachine::from_string("1101,2147483647,1,0,99");
+        let before = machine.clone();
+        assert_eq!(Err("2147483647 + 1 overflows at ip 0".to_string()), machine.try_step());
+        assert_eq!(before, machine);
+        assert_eq!(
+            Err("65536 * 65536 overflows at ip 0".to_string()),
+            IntMachine::from_string("1102,65536,65536,0,99").try_step()
+        );
+
+        // Jumps to the last address, where an output's operand can't be.
+        let mut machine = IntMachine::from_string("1101,0,4,2147483647,1105,1,2147483647,99");
+        machine.try_step().unwrap();
+        machine.try_step().unwrap();
+        assert_eq!(Err("Address 2147483647 + 1 is outside the program at ip 2147483647".to_string()), machine.try_step());
+    }
+
+    #[test]
+    fn test_try_execute() {
+        assert_eq!(Ok(3500), IntMachine::from_string("1,9,10,3,2,3,11,0,99,30,40,50").try_execute());
+        assert_eq!(Err("Address 3 is outside the program at ip 0".to_string()), IntMachine::from_string("1,0,0").try_execute());
+        assert_eq!(Err("No input left for the instruction at ip 0".to_string()), IntMachine::from_string("3,0,99").try_execute());
+
+        let mut machine = IntMachine::from_string("3,0,99");
+        machine.input.push_back(7);
+        assert_eq!(Ok(7), machine.try_execute());
+    }
+
+    #[test]
+    fn test_get_opcode_value() {
+        assert_eq!(2, get_opcode_value(1102));
+        assert_eq!(1, get_opcode_value(1));
+        assert_eq!(99, get_opcode_value(1099));
+    }
+
+    #[test]
+    fn test_get() {
+        let machine = IntMachine::from_string("1002,4,3,4,33");
+        let opcode = machine.get_direct(0);
+        
+        assert_eq!(1002, opcode);
+        assert_eq!(
+            33,
+            machine.get(opcode, Parameter::LEFT)
+        );
+        assert_eq!(
+            3,
+            machine.get(opcode, Parameter::RIGHT)
+        );
+    }
+}
+
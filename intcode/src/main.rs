@@ -0,0 +1,447 @@
+use std::env;
+use std::fs;
+use std::io::{stdin, Read};
+use std::process;
+
+use intcode::diff::{render_grid, MachineDiff};
+use intcode::{get_opcode_value, instruction_length, IntMachine, Opcode};
+
+const USAGE: &str = "Usage: intcode <program> [options]
+
+Options:
+  --input VALUES       input values, e.g. 1 or 1,5
+  --input-file PATH    read input values from a file
+  --stdin              read input values from stdin
+  --patch PATCHES      overwrite memory before running, e.g. 1=12,2=2
+  --format FORMAT      lines (default), csv or json
+  --trace LEVEL        none (default), ops or writes; printed to stderr
+  --max-steps N        stop with an error after N instructions
+  --dump-memory        include the final memory in the output
+  --diff               list the memory cells the run changed; printed to stderr
+  --grid               show the final memory as a table; printed to stderr
+  --why                trace the last output back to the write that produced it";
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Format {
+    Lines,
+    Csv,
+    Json,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+enum Trace {
+    None,
+    Ops,
+    Writes,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    program: String,
+    input: Vec<Opcode>,
+    read_stdin: bool,
+    patches: Vec<(i32, Opcode)>,
+    format: Format,
+    trace: Trace,
+    max_steps: Option<usize>,
+    dump_memory: bool,
+    diff: bool,
+    grid: bool,
+    why: bool,
+}
+
+struct Report {
+    output: Vec<Opcode>,
+    // What the program left at address 0, as day 5 used to report it.
+    result: Opcode,
+    steps: usize,
+    memory: Option<Vec<Opcode>>,
+}
+
+// Input values may be separated by commas, whitespace or newlines.
+fn parse_values(s: &str) -> Result<Vec<Opcode>, String> {
+    s.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.parse().map_err(|_| format!("{} is not a valid input value", value)))
+        .collect()
+}
+
+fn parse_patches(s: &str) -> Result<Vec<(i32, Opcode)>, String> {
+    s.split(',')
+        .filter(|patch| !patch.trim().is_empty())
+        .map(|patch| {
+            let mut parts = patch.splitn(2, '=');
+            let address = parts.next().unwrap().trim().parse();
+            let value = parts.next().map(|value| value.trim().parse());
+
+            match (address, value) {
+                (Ok(address), Some(Ok(value))) => Ok((address, value)),
+                _ => Err(format!("{} is not a valid patch; expected address=value", patch)),
+            }
+        })
+        .collect()
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        program: String::new(),
+        input: Vec::new(),
+        read_stdin: false,
+        patches: Vec::new(),
+        format: Format::Lines,
+        trace: Trace::None,
+        max_steps: None,
+        dump_memory: false,
+        diff: false,
+        grid: false,
+        why: false,
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--input" => options.input.extend(parse_values(value()?)?),
+            "--input-file" => {
+                let path = value()?;
+                let content = fs::read_to_string(path)
+                    .map_err(|err| format!("Unable to read {}: {}", path, err))?;
+                options.input.extend(parse_values(&content)?);
+            },
+            "--stdin" => options.read_stdin = true,
+            "--patch" => options.patches.extend(parse_patches(value()?)?),
+            "--format" => {
+                options.format = match value()?.as_str() {
+                    "lines" => Format::Lines,
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    other => return Err(format!("Unknown format {}", other)),
+                }
+            },
+            "--trace" => {
+                options.trace = match value()?.as_str() {
+                    "none" => Trace::None,
+                    "ops" => Trace::Ops,
+                    "writes" => Trace::Writes,
+                    other => return Err(format!("Unknown trace level {}", other)),
+                }
+            },
+            "--max-steps" => {
+                let steps = value()?;
+                options.max_steps = Some(
+                    steps.parse().map_err(|_| format!("{} is not a valid step limit", steps))?
+                );
+            },
+            "--dump-memory" => options.dump_memory = true,
+            "--diff" => options.diff = true,
+            "--grid" => options.grid = true,
+            "--why" => options.why = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            program if options.program.is_empty() => options.program = program.to_string(),
+            extra => return Err(format!("Unexpected argument {}", extra)),
+        }
+    }
+
+    if options.program.is_empty() {
+        return Err("No program given".to_string());
+    }
+    Ok(options)
+}
+
+fn format_instruction(machine: &IntMachine) -> String {
+    let op = machine.get_direct(machine.ip);
+    let mut instruction = format!("[{}] {}", machine.ip, op);
+
+    for offset in 1..instruction_length(op) {
+        match machine.ip.checked_add(offset).and_then(|address| machine.mem.get(&address)) {
+            Some(value) => instruction.push_str(&format!(" {}", value)),
+            None => instruction.push_str(" -"),
+        }
+    }
+    instruction
+}
+
+fn run(machine: &mut IntMachine, options: &Options) -> Result<Report, String> {
+    let mut steps = 0;
+
+    if options.trace >= Trace::Writes || options.why {
+        machine.record_history();
+    }
+
+    while get_opcode_value(machine.try_get_direct(machine.ip).map_err(|err| format!("{} at ip {}", err, machine.ip))?) != 99 {
+        if options.max_steps.is_some_and(|max| steps >= max) {
+            return Err(format!("Step limit of {} reached at ip {}", steps, machine.ip));
+        }
+        if machine.needs_input() {
+            return Err(format!("Program requested more input than supplied at ip {}", machine.ip));
+        }
+        if options.trace >= Trace::Ops {
+            eprintln!("{}", format_instruction(machine));
+        }
+
+        machine.try_step()?;
+        steps += 1;
+
+        if let Some(step) = machine.last_step().filter(|_| options.trace >= Trace::Writes) {
+            for (address, old) in &step.writes {
+                eprintln!(
+                    "    [{}]: {} -> {}",
+                    address,
+                    old.map_or("-".to_string(), |old| old.to_string()),
+                    machine.get_direct(*address)
+                );
+            }
+        }
+    }
+
+    Ok(Report {
+        output: machine.output.clone(),
+        result: machine.try_get_direct(0)?,
+        steps,
+        memory: if options.dump_memory { Some(memory_contents(machine)) } else { None },
+    })
+}
+
+// Memory from address 0 up to the highest address set, with gaps as 0.
+fn memory_contents(machine: &IntMachine) -> Vec<Opcode> {
+    let end = machine.mem.keys().filter(|address| **address >= 0).max().map_or(0, |max| max + 1);
+
+    (0..end).map(|address| *machine.mem.get(&address).unwrap_or(&0)).collect()
+}
+
+fn join(values: &[Opcode]) -> String {
+    values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(",")
+}
+
+fn format_report(report: &Report, format: Format) -> String {
+    let mut out = String::new();
+
+    match format {
+        Format::Lines => {
+            for value in &report.output {
+                out.push_str(&format!("{}\n", value));
+            }
+            out.push_str(&format!("Result: {}\n", report.result));
+            if let Some(memory) = &report.memory {
+                for (address, value) in memory.iter().enumerate() {
+                    out.push_str(&format!("[{}]: {}\n", address, value));
+                }
+            }
+        },
+        Format::Csv => {
+            out.push_str("kind,index,value\n");
+            for (index, value) in report.output.iter().enumerate() {
+                out.push_str(&format!("output,{},{}\n", index, value));
+            }
+            out.push_str(&format!("result,0,{}\n", report.result));
+            if let Some(memory) = &report.memory {
+                for (address, value) in memory.iter().enumerate() {
+                    out.push_str(&format!("memory,{},{}\n", address, value));
+                }
+            }
+        },
+        Format::Json => {
+            out.push_str(&format!(
+                "{{\"output\":[{}],\"result\":{},\"steps\":{}",
+                join(&report.output), report.result, report.steps
+            ));
+            if let Some(memory) = &report.memory {
+                out.push_str(&format!(",\"memory\":[{}]", join(memory)));
+            }
+            out.push_str("}\n");
+        },
+    }
+
+    out
+}
+
+// The --diff, --grid and --why reports, which compare the machine against
+// its state before the run.
+fn explain(initial: &IntMachine, machine: &mut IntMachine, options: &Options) -> String {
+    let mut out = String::new();
+
+    if options.diff {
+        let diff = MachineDiff::between(initial, machine);
+
+        if diff.is_empty() {
+            out.push_str("No changes\n");
+        } else {
+            out.push_str(&diff.to_string());
+        }
+    }
+    if options.grid {
+        out.push_str(&render_grid(initial, machine, 10));
+    }
+    if options.why {
+        match machine.explain_last_output() {
+            Some(origin) => {
+                out.push_str(&format!(
+                    "Output {} came from the instruction at {}\n",
+                    origin.value,
+                    origin.output_ip
+                ));
+                out.push_str(&match origin.source {
+                    Some((address, Some(writer))) =>
+                        format!("[{}] was last written by the instruction at {}\n", address, writer),
+                    Some((address, None)) => format!("[{}] was never written\n", address),
+                    None => "The operand was immediate\n".to_string(),
+                });
+            },
+            None => out.push_str("No output recorded\n"),
+        }
+    }
+
+    out
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let result = parse_args(&args).and_then(|mut options| {
+        if options.read_stdin {
+            let mut content = String::new();
+            stdin().read_to_string(&mut content).map_err(|err| err.to_string())?;
+            options.input.extend(parse_values(&content)?);
+        }
+
+        let source = fs::read_to_string(&options.program)
+            .map_err(|err| format!("Unable to read {}: {}", options.program, err))?;
+        let mut machine = IntMachine::try_from_string(&source)?;
+
+        machine.input.extend(&options.input);
+        for (address, value) in &options.patches {
+            machine.mem.insert(*address, *value);
+        }
+
+        let initial = machine.clone();
+        let report = run(&mut machine, &options)?;
+
+        eprint!("{}", explain(&initial, &mut machine, &options));
+        Ok(format_report(&report, options.format))
+    });
+
+    match result {
+        Ok(out) => print!("{}", out),
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            process::exit(1);
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(Ok(vec![1, 5, -3]), parse_values("1,5\n -3\n"));
+        assert!(parse_values("1,x").is_err());
+    }
+
+    #[test]
+    fn test_parse_patches() {
+        assert_eq!(Ok(vec![(1, 12), (2, 2)]), parse_patches("1=12,2=2"));
+        assert!(parse_patches("1=12,2").is_err());
+        assert!(parse_patches("a=1").is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(
+            &args("input.txt --input 1,5 --patch 1=12 --format json --trace ops --max-steps 10")
+        ).unwrap();
+
+        assert_eq!("input.txt", options.program);
+        assert_eq!(vec![1, 5], options.input);
+        assert_eq!(vec![(1, 12)], options.patches);
+        assert_eq!(Format::Json, options.format);
+        assert_eq!(Trace::Ops, options.trace);
+        assert_eq!(Some(10), options.max_steps);
+        assert!(!options.dump_memory);
+
+        assert!(parse_args(&args("--input 1")).is_err());
+        assert!(parse_args(&args("a.txt --format xml")).is_err());
+        assert!(parse_args(&args("a.txt --max-steps")).is_err());
+        assert!(parse_args(&args("a.txt b.txt")).is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let options = parse_args(&args("p --dump-memory")).unwrap();
+        let mut machine = IntMachine::from_string("3,0,4,0,99");
+        machine.input.push_back(42);
+        let report = run(&mut machine, &options).unwrap();
+
+        assert_eq!(vec![42], report.output);
+        assert_eq!(42, report.result);
+        assert_eq!(2, report.steps);
+        assert_eq!(Some(vec![42, 0, 4, 0, 99]), report.memory);
+    }
+
+    #[test]
+    fn test_run_limits() {
+        let options = parse_args(&args("p --max-steps 1")).unwrap();
+
+        assert!(run(&mut IntMachine::from_string("1,0,0,0,1,0,0,0,99"), &options).is_err());
+        assert!(run(&mut IntMachine::from_string("3,0,99"), &options).is_err());
+    }
+
+    #[test]
+    fn test_run_malformed() {
+        let options = parse_args(&args("p")).unwrap();
+        let error = |program: &str| run(&mut IntMachine::from_string(program), &options).err();
+
+        assert_eq!(Some("Address 9 is outside the program at ip 0".to_string()), error("1,0,9,0,99"));
+        assert_eq!(Some("Invalid opcode 42 at ip 0".to_string()), error("42,99"));
+        assert_eq!(Some("Address 50 is outside the program at ip 50".to_string()), error("1105,1,50"));
+        assert_eq!(Some("2147483647 + 1 overflows at ip 0".to_string()), error("1101,2147483647,1,0,99"));
+    }
+
+    #[test]
+    fn test_format_report() {
+        let report = Report{ output: vec![1, 2], result: 99, steps: 3, memory: Some(vec![99]) };
+
+        assert_eq!("1\n2\nResult: 99\n[0]: 99\n", format_report(&report, Format::Lines));
+        assert_eq!(
+            "kind,index,value\noutput,0,1\noutput,1,2\nresult,0,99\nmemory,0,99\n",
+            format_report(&report, Format::Csv)
+        );
+        assert_eq!(
+            "{\"output\":[1,2],\"result\":99,\"steps\":3,\"memory\":[99]}\n",
+            format_report(&report, Format::Json)
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let options = parse_args(&args("p --diff --why")).unwrap();
+        let mut machine = IntMachine::from_string("1101,2,3,9,4,9,99,0,0,0");
+        let initial = machine.clone();
+        run(&mut machine, &options).unwrap();
+
+        assert_eq!(
+            "ip: 0 -> 6\n[9]: 0 -> 5\noutput differs from index 0: [] -> [5]\n\
+            Output 5 came from the instruction at 4\n\
+            [9] was last written by the instruction at 0\n",
+            explain(&initial, &mut machine, &options)
+        );
+    }
+
+    #[test]
+    fn test_format_instruction() {
+        let machine = IntMachine::from_string("1101,2,3,9,99");
+
+        assert_eq!("[0] 1101 2 3 9", format_instruction(&machine));
+    }
+}